@@ -0,0 +1,6 @@
+//! Benches every solved day on its puzzle input, e.g. `cargo bench -- 05` for day 5.
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+fn main() {
+    advent_of_code::template::bench::main(DAYS);
+}
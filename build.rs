@@ -0,0 +1,37 @@
+//! Lists the solutions in `src/bin/` for the `days` bench target, see `benches/days.rs`.
+use std::fmt::Write;
+use std::path::Path;
+use std::{env, fs};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let bin_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap())
+        .join("src")
+        .join("bin");
+    let mut days: Vec<(u8, String)> = fs::read_dir(&bin_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let day = path.file_stem()?.to_str()?.parse().ok()?;
+            (path.extension()? == "rs").then(|| (day, path.display().to_string()))
+        })
+        .collect();
+    days.sort();
+
+    // the warnings of the solutions are already reported when building their bins.
+    let mut code = String::new();
+    for (day, path) in &days {
+        writeln!(code, "#[allow(warnings, clippy::all)]").unwrap();
+        writeln!(code, "#[path = {path:?}]").unwrap();
+        writeln!(code, "mod day_{day:02};").unwrap();
+    }
+    code += "\nconst DAYS: &[(u8, advent_of_code::template::bench::Parts)] = &[\n";
+    for (day, _) in &days {
+        writeln!(code, "    ({day}, day_{day:02}::bench_parts),").unwrap();
+    }
+    code += "];\n";
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("days.rs");
+    fs::write(out, code).unwrap();
+}
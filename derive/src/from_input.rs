@@ -0,0 +1,183 @@
+//! Expansion of `#[derive(FromInput)]`.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, Ident, LitStr};
+
+/// A piece of the pattern given with `#[input("...")]`.
+enum Segment {
+    Literal(String),
+    Field(Ident, Kind),
+}
+
+/// How the text of a field is parsed.
+enum Kind {
+    Single,
+    List(Separator),
+}
+
+enum Separator {
+    Whitespace,
+    Comma,
+}
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let fields = named_fields(input)?;
+
+    let attr = input
+        .attrs
+        .iter()
+        .find(|a| a.path().is_ident("input"))
+        .ok_or_else(|| syn::Error::new_spanned(name, "missing `#[input(\"...\")]` pattern"))?;
+    let pattern: LitStr = attr.parse_args()?;
+    let segments = parse_pattern(&pattern)?;
+
+    // every field of the struct must be filled by the pattern, once.
+    let mut seen = vec![];
+    for segment in &segments {
+        if let Segment::Field(field, _) = segment {
+            if !fields.contains(field) {
+                return Err(syn::Error::new_spanned(
+                    &pattern,
+                    format!("`{name}` has no field `{field}`"),
+                ));
+            }
+            if seen.contains(&field) {
+                return Err(syn::Error::new_spanned(
+                    &pattern,
+                    format!("field `{field}` appears twice"),
+                ));
+            }
+            seen.push(field);
+        }
+    }
+    if let Some(missing) = fields.iter().find(|f| !seen.contains(f)) {
+        return Err(syn::Error::new_spanned(
+            &pattern,
+            format!("field `{missing}` is missing from the pattern"),
+        ));
+    }
+
+    let steps = segments.iter().enumerate().map(|(i, segment)| match segment {
+        Segment::Literal(text) if i == 0 => quote! {
+            let rest = rest
+                .strip_prefix(#text)
+                .ok_or_else(|| ParseError::at(value, rest, ::std::format!("expecting `{}`", #text)))?;
+        },
+        // literals after a field are consumed with the field.
+        Segment::Literal(_) => quote! {},
+        Segment::Field(field, kind) => {
+            let text = format_ident!("{}_text", field);
+            let label = field.to_string();
+            let split = match segments.get(i + 1) {
+                Some(Segment::Literal(next)) => quote! {
+                    let (#text, rest) = rest.split_once(#next).ok_or_else(|| {
+                        ParseError::at(value, rest, ::std::format!("expecting `{}` after `{}`", #next, #label))
+                    })?;
+                },
+                _ => quote! {
+                    let (#text, rest) = (rest, &rest[rest.len()..]);
+                },
+            };
+            let parse = match kind {
+                Kind::Single => quote! {
+                    let #text = #text.trim();
+                    let #field = #text.parse().map_err(|_| {
+                        ParseError::at(value, #text, ::std::format!("`{}`: cannot parse `{}`", #label, #text))
+                    })?;
+                },
+                Kind::List(separator) => {
+                    let items = match separator {
+                        Separator::Whitespace => quote! { #text.split_ascii_whitespace() },
+                        Separator::Comma => quote! { #text.split(',').map(::core::primitive::str::trim) },
+                    };
+                    quote! {
+                        let #field = #items
+                            .map(|item| item.parse().map_err(|_| {
+                                ParseError::at(value, item, ::std::format!("`{}`: cannot parse `{}`", #label, item))
+                            }))
+                            .collect::<::core::result::Result<_, ParseError>>()?;
+                    }
+                }
+            };
+            quote! { #split #parse }
+        }
+    });
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::core::convert::TryFrom<&::core::primitive::str> for #name #ty_generics #where_clause {
+            type Error = ::advent_of_code::ParseError;
+
+            fn try_from(value: &::core::primitive::str) -> ::core::result::Result<Self, Self::Error> {
+                use ::advent_of_code::ParseError;
+                let rest = value;
+                #(#steps)*
+                if !rest.trim().is_empty() {
+                    return ::core::result::Result::Err(ParseError::at(
+                        value,
+                        rest,
+                        "unexpected trailing input",
+                    ));
+                }
+                ::core::result::Result::Ok(Self { #(#fields),* })
+            }
+        }
+    })
+}
+
+fn named_fields(input: &DeriveInput) -> syn::Result<Vec<Ident>> {
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => Ok(fields
+                .named
+                .iter()
+                .filter_map(|f| f.ident.clone())
+                .collect()),
+            _ => Err(syn::Error::new_spanned(
+                &input.ident,
+                "`FromInput` needs a struct with named fields",
+            )),
+        },
+        _ => Err(syn::Error::new_spanned(
+            &input.ident,
+            "`FromInput` can only be derived for structs",
+        )),
+    }
+}
+
+fn parse_pattern(pattern: &LitStr) -> syn::Result<Vec<Segment>> {
+    let error = |message: &str| syn::Error::new_spanned(pattern, message);
+    let mut segments = vec![];
+    let mut rest = pattern.value();
+
+    while !rest.is_empty() {
+        if let Some(field) = rest.strip_prefix('{') {
+            let (field, after) = field.split_once('}').ok_or_else(|| error("unclosed `{`"))?;
+            let (name, kind) = match field.split_once(':') {
+                Some((name, kind)) => (name.trim(), kind.trim()),
+                None => (field.trim(), ""),
+            };
+            let kind = match kind {
+                "" => Kind::Single,
+                "ws-list" => Kind::List(Separator::Whitespace),
+                "comma-list" => Kind::List(Separator::Comma),
+                _ => return Err(error(&format!("unknown kind `{kind}` for field `{name}`"))),
+            };
+            if matches!(segments.last(), Some(Segment::Field(..))) {
+                return Err(error("two fields must be separated by some text"));
+            }
+            let name = syn::parse_str::<Ident>(name)
+                .map_err(|_| error(&format!("`{name}` is not a field name")))?;
+            segments.push(Segment::Field(name, kind));
+            rest = after.to_string();
+        } else {
+            let end = rest.find('{').unwrap_or(rest.len());
+            segments.push(Segment::Literal(rest[..end].to_string()));
+            rest = rest[end..].to_string();
+        }
+    }
+
+    Ok(segments)
+}
@@ -0,0 +1,73 @@
+//! Expansion of `#[derive(GridCell)]`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, LitChar};
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "`GridCell` can only be derived for enums",
+        ));
+    };
+
+    let mut variants = vec![];
+    let mut chars: Vec<LitChar> = vec![];
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "`GridCell` variants cannot have fields",
+            ));
+        }
+        let attr = variant
+            .attrs
+            .iter()
+            .find(|a| a.path().is_ident("cell"))
+            .ok_or_else(|| {
+                syn::Error::new_spanned(variant, "missing `#[cell('...')]` character")
+            })?;
+        let c: LitChar = attr.parse_args()?;
+        if chars.iter().any(|other| other.value() == c.value()) {
+            return Err(syn::Error::new_spanned(
+                &c,
+                format!("{:?} is used by two variants", c.value()),
+            ));
+        }
+        variants.push(&variant.ident);
+        chars.push(c);
+    }
+
+    Ok(quote! {
+        impl ::core::convert::TryFrom<::core::primitive::char> for #name {
+            type Error = ::advent_of_code::grid::UnknownCell;
+
+            fn try_from(
+                c: ::core::primitive::char,
+            ) -> ::core::result::Result<Self, Self::Error> {
+                match c {
+                    #(#chars => ::core::result::Result::Ok(Self::#variants),)*
+                    _ => ::core::result::Result::Err(::advent_of_code::grid::UnknownCell(c)),
+                }
+            }
+        }
+
+        impl ::core::convert::From<#name> for ::core::primitive::char {
+            fn from(cell: #name) -> ::core::primitive::char {
+                match cell {
+                    #(#name::#variants => #chars,)*
+                }
+            }
+        }
+
+        impl ::core::fmt::Display for #name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self {
+                    #(Self::#variants => ::core::fmt::Write::write_char(f, #chars),)*
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,58 @@
+//! Derive macros for the `advent_of_code` library. They are re-exported by it, use them from there.
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+mod from_input;
+mod grid_cell;
+
+/// Generates a `TryFrom<&str>` implementation from a pattern describing the line.
+///
+/// The pattern is given with `#[input("...")]`. Text between braces is a field of the struct,
+/// everything else must appear as is in the line. A field can be followed by a kind:
+///
+/// - `{field}` parses the trimmed text with [`FromStr`](std::str::FromStr),
+/// - `{field: ws-list}` parses each whitespace-separated item into a `Vec`,
+/// - `{field: comma-list}` parses each comma-separated item into a `Vec`.
+///
+/// Two fields must be separated by some text. Errors are `advent_of_code::ParseError`s that
+/// point to the field which failed, or to where the text of the pattern was expected.
+///
+/// ```ignore
+/// #[derive(FromInput)]
+/// #[input("Card {number}: {winning: ws-list} | {numbers: ws-list}")]
+/// struct Card {
+///     number: u32,
+///     winning: Vec<u32>,
+///     numbers: Vec<u32>,
+/// }
+/// ```
+#[proc_macro_derive(FromInput, attributes(input))]
+pub fn derive_from_input(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    from_input::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Maps the variants of a fieldless enum to the characters of a grid.
+///
+/// Every variant needs a `#[cell('...')]` character, and two variants cannot share one. This
+/// generates `TryFrom<char>` (failing with `advent_of_code::grid::UnknownCell`), `From<Enum>`
+/// for `char` and `Display`, so that the enum can be used with `Grid::parse_cells`.
+///
+/// ```ignore
+/// #[derive(Clone, Copy, GridCell)]
+/// enum Tile {
+///     #[cell('.')]
+///     Empty,
+///     #[cell('#')]
+///     Rock,
+/// }
+/// ```
+#[proc_macro_derive(GridCell, attributes(cell))]
+pub fn derive_grid_cell(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    grid_cell::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -1,3 +1,6 @@
+use advent_of_code::param::param;
+use advent_of_code::ParseError;
+
 advent_of_code::solution!(2);
 
 struct Game {
@@ -5,40 +8,51 @@ struct Game {
     played: Vec<(usize, usize, usize)>,
 }
 
-impl From<&str> for Game {
-    fn from(value: &str) -> Self {
-        let (id_str, content) = value.split_once(": ").expect("No :");
-        let id = id_str.split_once(" ").expect("bad ID")
-            .1
-            .parse::<usize>().expect("ID is not a number");
+impl TryFrom<&str> for Game {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (id_str, content) = value.split_once(": ")
+            .ok_or_else(|| ParseError::at(value, value, "expecting `Game <id>: <sets>`"))?;
+        let id = id_str.strip_prefix("Game ")
+            .and_then(|id| id.parse::<usize>().ok())
+            .ok_or_else(|| ParseError::at(value, id_str, "bad ID"))?;
 
         let mut played = vec![];
         for set in content.split("; ") {
             let mut current = (0, 0, 0);
             for cubes in set.split(", ") {
-                let (q_str, color) = cubes.split_once(" ").expect("bad cubes");
-                let q = q_str.parse::<usize>().expect("Amount of cubes is not a number");
+                let (q_str, color) = cubes.split_once(" ")
+                    .ok_or_else(|| ParseError::at(value, cubes, "expecting `<amount> <color>`"))?;
+                let q = q_str.parse::<usize>()
+                    .map_err(|_| ParseError::at(value, q_str, "amount of cubes is not a number"))?;
                 match color {
                     "red" => { current.0 += q; }
                     "green" => { current.1 += q; }
                     "blue" => { current.2 += q; }
-                    _ => panic!("unknown color")
+                    _ => return Err(ParseError::at(value, color, "unknown color")),
                 }
             }
             played.push(current);
         }
 
-        Game { id, played }
+        Ok(Game { id, played })
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let r_max = 12;
-    let g_max = 13;
-    let b_max = 14;
-    let games = input
+fn parse_games(input: &str) -> Result<Vec<Game>, ParseError> {
+    input
         .lines()
-        .map(|line| Game::from(line))
+        .map(|line| Game::try_from(line).map_err(|e| e.within(input, line)))
+        .collect()
+}
+
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    let r_max = param("red", 12);
+    let g_max = param("green", 13);
+    let b_max = param("blue", 14);
+    let games = parse_games(input)?
+        .iter()
         .filter(|game|
             game.played.iter().max_by_key(|(r, _, _)| *r).unwrap_or(&(0, 0, 0)).0 <= r_max
                 && game.played.iter().max_by_key(|(_, g, _)| *g).unwrap_or(&(0, 0, 0)).1 <= g_max
@@ -48,13 +62,12 @@ pub fn part_one(input: &str) -> Option<usize> {
         .sum::<usize>()
     ;
 
-    Some(games)
+    Ok(games)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let games = input
-        .lines()
-        .map(|line| Game::from(line))
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    let games = parse_games(input)?
+        .iter()
         .map(|game|
             (game.played.iter().max_by_key(|(r, _, _)| *r).unwrap_or(&(0, 0, 0)).0,
              game.played.iter().max_by_key(|(_, g, _)| *g).unwrap_or(&(0, 0, 0)).1,
@@ -64,22 +77,36 @@ pub fn part_two(input: &str) -> Option<usize> {
         .sum::<usize>()
     ;
 
-    Some(games)
+    Ok(games)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::param::with_params;
 
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(8));
+        assert_eq!(result, Ok(8));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2286));
+        assert_eq!(result, Ok(2286));
+    }
+
+    #[test]
+    fn test_part_one_with_fewer_cubes() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let result = with_params(&[("red", "4"), ("green", "3"), ("blue", "6")], || part_one(&input));
+        assert_eq!(result, Ok(3));
+    }
+
+    #[test]
+    fn test_unknown_color() {
+        let result = part_one("Game 1: 3 blue\nGame 2: 4 purple, 1 red");
+        assert_eq!(result, Err(ParseError::new(2, 11, "unknown color")));
     }
 }
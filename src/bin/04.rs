@@ -1,47 +1,35 @@
+use advent_of_code::fuzz::Rng;
+use advent_of_code::parse;
+use advent_of_code::{FromInput, ParseError};
 use itertools::Itertools;
 
-advent_of_code::solution!(4);
+advent_of_code::solution!(4, part_one, part_two, generate);
 
+#[derive(Debug, FromInput)]
+#[input("Card {number}: {winning: ws-list} | {numbers: ws-list}")]
 struct Card {
     number: u32,
     winning: Vec<u32>,
     numbers: Vec<u32>,
 }
 
-impl From<&str> for Card {
-    fn from(value: &str) -> Self {
-        let (card, numbers_str) = value.split_once(": ").unwrap();
-        let number = card.split_ascii_whitespace().collect_vec()[1].parse::<u32>().unwrap();
-        let mut winning = vec![];
-        let mut numbers = vec![];
-        let (winning_str, n) = numbers_str.split_once(" | ").unwrap();
-        winning_str.split_ascii_whitespace().for_each(|nb| {
-            winning.push(nb.parse::<u32>().unwrap())
-        });
-        n.split_ascii_whitespace().for_each(|nb| {
-            numbers.push(nb.parse::<u32>().unwrap())
-        });
-        Card { number, winning, numbers }
-    }
+fn parse_cards(input: &str) -> Result<Vec<Card>, ParseError> {
+    parse::lines(input)
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let cards = input.lines()
-        .map(|l| Card::from(l))
-        .collect_vec();
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    let cards = parse_cards(input)?;
     let score = cards.iter()
         .map(|c| {
             let w = c.numbers.iter().filter(|n| c.winning.contains(*n)).count();
             if w == 0 { 0 } else { 2_u32.pow((w - 1) as u32) }
         })
         .sum::<u32>();
-    Some(score)
+    Ok(score)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let cards = input.lines()
-        .map(|l| Card::from(l))
-        .collect_vec();
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    let cards = parse_cards(input)?;
     let mut count: Vec<u32> = vec![1; cards.len()];
     cards.iter()
         .for_each(|c| {
@@ -50,22 +38,60 @@ pub fn part_two(input: &str) -> Option<u32> {
                 count[i as usize] += count[c.number as usize - 1]
             }
         });
-    Some(count.iter().sum())
+    Ok(count.iter().sum())
+}
+
+/// Generates `size` cards, with 5 winning numbers and 8 numbers each, aligned like the input.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut numbers = |count: usize| (0..count).map(|_| format!("{:>2}", 1 + rng.below(20))).join(" ");
+    (1..=size)
+        .map(|card| format!("Card {card:>3}: {} | {}", numbers(5), numbers(8)))
+        .join("\n")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::fuzz::{check_parsers, check_variants};
+    use advent_of_code::parse::Parser;
+
+    /// Parses the cards with [`Parser`], to check the parser generated by `FromInput` against.
+    fn parse_cards_by_hand(input: &str) -> Result<Vec<Card>, ParseError> {
+        input
+            .lines()
+            .map(|line| {
+                let mut card = Parser::new(line);
+                card.tag("Card")?;
+                let number = card.number()?;
+                card.tag(":")?;
+                let winning = card.until("|")?.numbers()?;
+                let numbers = card.numbers()?;
+                Ok(Card { number, winning, numbers })
+            })
+            .collect()
+    }
 
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(13));
+        assert_eq!(result, Ok(13));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(30));
+        assert_eq!(result, Ok(30));
+    }
+
+    #[test]
+    fn test_parsers_agree() {
+        check_parsers(&[("derive", &parse_cards), ("by_hand", &parse_cards_by_hand)], generate, 200);
+    }
+
+    #[test]
+    fn test_parts_do_not_panic() {
+        check_variants(&[("part_one", &part_one)], generate, 200);
+        check_variants(&[("part_two", &part_two)], generate, 200);
     }
 }
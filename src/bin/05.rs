@@ -2,6 +2,10 @@ use std::ops::Range;
 
 use itertools::Itertools;
 
+use advent_of_code::parse::{self, Parser};
+use advent_of_code::ranges::RangeSet;
+use advent_of_code::{aoc_debug, aoc_trace, FromInput, ParseError};
+
 advent_of_code::solution!(5);
 
 struct Converter {
@@ -17,29 +21,16 @@ impl Converter {
             None => n,
         }
     }
-    fn convert_range(&self, range: &Range<usize>) -> Vec<Range<usize>> {
-        let mut old = vec![range.clone()];
-        let mut new = vec![];
-        for rule in &self.rules {
-            let mut old_2 = vec![];
-            for r in old {
-                match rule.convert_range(&r) {
-                    Some((converted_range, new_range)) => {
-                        new.push(new_range);
-                        if converted_range.start > r.start { old_2.push(range.start..converted_range.start); }
-                        if converted_range.end < r.end { old_2.push(converted_range.end..range.end); }
-                    }
-                    None => { old_2.push(r); }
-                }
-            }
-            old = old_2;
-        }
-        // println!("range: {:?} -> old {:?} /  new {:?}", range, old, new);
-        new.append(&mut old);
-        new
+    fn convert_range(&self, ranges: &RangeSet) -> RangeSet {
+        let rules = self.rules.iter().map(Rule::as_offset).collect_vec();
+        let converted = ranges.map(&rules);
+        aoc_trace!("{ranges} -> {converted}");
+        converted
     }
 }
 
+#[derive(FromInput)]
+#[input("{start_2} {start_1} {len}")]
 struct Rule {
     start_1: usize,
     start_2: usize,
@@ -53,52 +44,36 @@ impl Rule {
         return self.start_2 + n - self.start_1
     }
 
-    fn convert_range(&self, r: &Range<usize>) -> Option<(Range<usize>, Range<usize>)> {
-        let start = r.start.max(self.start_1);
-        if start < r.end && start < self.start_1 + self.len {
-            let end = r.end.min(self.start_1 + self.len);
-            let len = end - start;
-            let mapped_start = self.start_2 + start - self.start_1;
-            Some((start..end, mapped_start..mapped_start+len))
-        } else {
-            None
-        }
+    /// Returns the source range of the rule, with the offset it adds to the numbers in it.
+    fn as_offset(&self) -> (Range<i64>, i64) {
+        let start = self.start_1 as i64;
+        (start..start + self.len as i64, self.start_2 as i64 - start)
     }
 }
 
-impl From<&str> for Converter {
-    fn from(value: &str) -> Self {
+impl TryFrom<&str> for Converter {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
         let mut lines = value.lines();
-        if let Some(_) = lines.next() {
-            let rules = lines.map(|l| Rule::from(l)).collect_vec();
-            Self { rules }
+        if lines.next().is_some_and(|header| header.ends_with("map:")) {
+            let rules = lines
+                .map(|l| Rule::try_from(l).map_err(|e| e.within(value, l)))
+                .collect::<Result<Vec<Rule>, ParseError>>()?;
+            Ok(Self { rules })
         } else {
-            panic!("Malformed map");
-        }
-    }
-}
-
-impl From<&str> for Rule {
-    fn from(value: &str) -> Self {
-        let nb = value
-            .split_ascii_whitespace()
-            .map(|n| n.parse::<usize>().unwrap())
-            .collect_vec();
-        if nb.len() != 3 { panic!("Malformed rule"); }
-        Self {
-            start_1: nb[1],
-            start_2: nb[0],
-            len: nb[2],
+            Err(ParseError::at(value, value, "malformed map: expecting `<name> map:`"))
         }
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let (seeds, converters) = parse_input(input);
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    let (seeds_line, seeds, converters) = parse_input(input)?;
 
     seeds.iter()
         .map(|seed| convert_seed(&converters, seed))
         .min()
+        .ok_or_else(|| ParseError::at(input, &seeds_line[seeds_line.len()..], "expecting seeds"))
 }
 
 fn convert_seed(converters: &Vec<Converter>, seed: &usize) -> usize {
@@ -107,45 +82,47 @@ fn convert_seed(converters: &Vec<Converter>, seed: &usize) -> usize {
         .fold(*seed, |acc, c| c.convert_min(acc))
 }
 
-fn convert_seed_range(converters: &Vec<Converter>, range: &Range<usize>) -> Vec<Range<usize>> {
+fn convert_seed_range(converters: &Vec<Converter>, range: &Range<usize>) -> RangeSet {
+    let seeds = RangeSet::from(range.start as i64..range.end as i64);
     converters
         .iter()
-        .fold(vec![range.clone()], |acc, c| acc.iter().flat_map(|r| c.convert_range(r)).collect_vec())
+        .fold(seeds, |acc, c| c.convert_range(&acc))
 }
 
-fn parse_input(input: &str) -> (Vec<usize>, Vec<Converter>) {
-    let mut parts = input.split("\n\n");
-    let seeds = parts
-        .next().unwrap()
-        .split_once(": ").unwrap().1
-        .split_ascii_whitespace()
-        .map(|s| s.parse::<usize>().unwrap())
-        .collect_vec();
+/// Parses the seeds, with the line they are on, and the maps.
+fn parse_input(input: &str) -> Result<(&str, Vec<usize>, Vec<Converter>), ParseError> {
+    let mut parts = parse::blocks(input);
+    let seeds_str = parts.next().unwrap_or_default();
+    let seeds = Parser::new(seeds_str)
+        .tag("seeds:")?
+        .numbers()
+        .map_err(|e| e.within(input, seeds_str))?;
     let converters = parts
-        .map(|part| Converter::from(part))
-        .collect_vec();
-    (seeds, converters)
+        .map(|part| Converter::try_from(part).map_err(|e| e.within(input, part)))
+        .collect::<Result<Vec<Converter>, ParseError>>()?;
+    Ok((seeds_str, seeds, converters))
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let (seeds, converters) = parse_input(input);
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    let (seeds_line, seeds, converters) = parse_input(input)?;
+    if seeds.len() % 2 != 0 {
+        let last = seeds_line.trim_end().rsplit(' ').next().unwrap_or(seeds_line);
+        return Err(ParseError::at(input, last, "seeds must come in pairs of start and length"));
+    }
     seeds
-        .chunks(2).into_iter()
-        .flat_map(|arr| match arr {
-            [s1, s2] => convert_seed_range(&converters, &(*s1..*s1+*s2)),
-            _ => panic!(),
+        .chunks(2)
+        .filter_map(|arr| {
+            let locations = convert_seed_range(&converters, &(arr[0]..arr[0] + arr[1]));
+            aoc_debug!("seeds {}..{}: {} locations, lowest {:?}", arr[0], arr[0] + arr[1], locations.len(), locations.min());
+            locations.min()
         })
-        .map(|r| r.start)
         .min()
-}
-
-#[test]
-fn test() {
-    let r = convert_seed_range(&vec![Converter { rules: vec![
-        Rule { start_1: 56, start_2: 60, len: 37, },
-        Rule { start_1: 93, start_2: 56, len: 4, },
-    ],  }], &(90..99));
-    println!("{:?}", r);
+        .map(|n| n as usize)
+        .ok_or_else(|| if seeds.is_empty() {
+            ParseError::at(input, &seeds_line[seeds_line.len()..], "expecting seeds")
+        } else {
+            ParseError::at(input, seeds_line, "every range of seeds is empty")
+        })
 }
 
 #[cfg(test)]
@@ -155,12 +132,39 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(35));
+        assert_eq!(result, Ok(35));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(46));
+        assert_eq!(result, Ok(46));
+    }
+
+    #[test]
+    fn test_range_split_by_rules() {
+        let converter = Converter { rules: vec![
+            Rule { start_1: 56, start_2: 60, len: 37, },
+            Rule { start_1: 93, start_2: 56, len: 4, },
+        ] };
+        let result = convert_seed_range(&vec![converter], &(90..99));
+        assert_eq!(result, RangeSet::from_iter([56..60, 94..99]));
+    }
+
+    #[test]
+    fn test_malformed_rule() {
+        let result = part_one("seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 50");
+        assert_eq!(result, Err(ParseError::new(5, 4, "expecting ` ` after `start_1`")));
+    }
+
+    #[test]
+    fn test_malformed_seeds() {
+        let maps = "\n\nseed-to-soil map:\n50 98 2";
+        let result = part_one(&format!("seeds:{maps}"));
+        assert_eq!(result, Err(ParseError::new(1, 7, "expecting seeds")));
+        let result = part_two(&format!("seeds: 79 14 55{maps}"));
+        assert_eq!(result, Err(ParseError::new(1, 14, "seeds must come in pairs of start and length")));
+        let result = part_two(&format!("seeds: 79 0{maps}"));
+        assert_eq!(result, Err(ParseError::new(1, 1, "every range of seeds is empty")));
     }
 }
@@ -2,20 +2,24 @@ use std::str::Lines;
 
 use itertools::Itertools;
 
+use advent_of_code::{parse, ParseError};
+
 advent_of_code::solution!(6);
 
 #[derive(Debug)]
 struct Race { time: usize, distance: usize }
 
-fn from(value: &str) -> Vec<Race> {
+fn from(value: &str) -> Result<Vec<Race>, ParseError> {
     let mut lines = value.lines();
-    let time = get_line_numbers(&mut lines);
-    let distance = get_line_numbers(&mut lines);
-    time.iter().zip(distance).map(|(t, d)| Race { time: *t, distance: d }).collect_vec()
+    let time = get_line_numbers(value, &mut lines)?;
+    let distance = get_line_numbers(value, &mut lines)?;
+    Ok(time.iter().zip(distance).map(|(t, d)| Race { time: *t, distance: d }).collect_vec())
 }
 
-fn get_line_numbers(lines: &mut Lines) -> Vec<usize> {
-    lines.next().unwrap().split_ascii_whitespace().filter_map(|n| n.parse::<usize>().ok()).collect_vec()
+fn get_line_numbers(input: &str, lines: &mut Lines) -> Result<Vec<usize>, ParseError> {
+    let line = lines.next()
+        .ok_or_else(|| ParseError::at(input, &input[input.len()..], "expecting a line of numbers"))?;
+    parse::numbers(line).collect::<Result<_, _>>().map_err(|e| e.within(input, line))
 }
 
 fn get_line_number(lines: &mut Lines) -> usize {
@@ -28,9 +32,10 @@ impl Race {
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let races = from(input);
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    let races = from(input)?;
     races.iter().map(|r| r.ways_to_beat()).product1()
+        .ok_or_else(|| ParseError::at(input, input, "expecting times of races"))
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
@@ -48,7 +53,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(288));
+        assert_eq!(result, Ok(288));
     }
 
     #[test]
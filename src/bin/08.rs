@@ -1,68 +1,151 @@
 use std::collections::HashMap;
-use std::ops::Deref;
-use std::str::Lines;
+use std::fmt::Display;
 
 use itertools::Itertools;
 
+use advent_of_code::cycle::{self, Cycle};
+use advent_of_code::math::{self, OffsetCycle};
+use advent_of_code::parse::{self, Parser};
+use advent_of_code::ParseError;
+
 advent_of_code::solution!(8);
 
-fn parse_input(input: Lines) -> HashMap<String, (String, String)> {
-    let mut map = HashMap::new();
-    input.for_each(|line| {
-        let key = line[..3].to_string();
-        let l = line[7..10].to_string();
-        let r = line[12..15].to_string();
-        map.insert(key, (l, r));
-    });
-    map
+type Network = HashMap<String, (String, String)>;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    Parse(ParseError),
+    /// A node that is reached but never defined.
+    UndefinedNode(String),
+    /// The ghosts never all stand on a `Z` node at the same time.
+    NoSolution,
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let mut lines = input.lines();
-    let dir = lines.next().unwrap().chars().collect_vec();
-    lines.next();
-    let map = parse_input(lines);
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Self::Parse(error)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(error) => write!(f, "{error}"),
+            Error::UndefinedNode(node) => write!(f, "the node `{node}` is not defined"),
+            Error::NoSolution => write!(f, "the ghosts never all stand on a `Z` node at the same time"),
+        }
+    }
+}
+
+fn parse_node(line: &str) -> Result<(String, (String, String)), ParseError> {
+    let mut node = Parser::new(line);
+    let key = node.word()?;
+    node.tag("=")?.tag("(")?;
+    let l = node.until(",")?.word()?;
+    let r = node.until(")")?.word()?;
+    node.end()?;
+    Ok((key.to_string(), (l.to_string(), r.to_string())))
+}
+
+/// Parses the instructions and the network, in which every node that is reached is defined.
+fn parse_input(input: &str) -> Result<(Vec<char>, Network), Error> {
+    let mut blocks = parse::blocks(input);
+    let instructions = blocks.next().unwrap_or_default();
+    if let Some(i) = instructions.find(|c| c != 'L' && c != 'R') {
+        return Err(ParseError::at(input, &instructions[i..], "expecting `L` or `R`").into());
+    }
+    if instructions.is_empty() {
+        return Err(ParseError::at(input, instructions, "expecting instructions").into());
+    }
+    let dir = instructions.chars().collect_vec();
+
+    let network = blocks.next().unwrap_or_default();
+    let map = network.lines()
+        .map(|line| parse_node(line).map_err(|e| e.within(input, line)))
+        .collect::<Result<Network, ParseError>>()?;
+    if let Some(node) = map.values().flat_map(|(l, r)| [l, r]).find(|n| !map.contains_key(*n)) {
+        return Err(Error::UndefinedNode(node.clone()));
+    }
+    Ok((dir, map))
+}
+
+pub fn part_one(input: &str) -> Result<usize, Error> {
+    let (dir, map) = parse_input(input)?;
 
     let mut current = &"AAA".to_string();
+    if !map.contains_key(current) {
+        return Err(Error::UndefinedNode(current.clone()));
+    }
     let mut i = 0;
     let instr = dir.len();
     loop {
         if current == "ZZZ" { break; }
-        let next = map.get(current).unwrap();
-        current = match dir[i % instr] {
-            'L' => &next.0,
-            'R' => &next.1,
-            _ => panic!("Wrong instr")
-        };
+        // every node that is reached is defined, see `parse_input`.
+        let next = &map[current];
+        current = if dir[i % instr] == 'L' { &next.0 } else { &next.1 };
         i += 1;
     }
-    Some(i)
+    Ok(i)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let mut lines = input.lines();
-    let dir = lines.next().unwrap().chars().collect_vec();
-    lines.next();
-    let map = parse_input(lines);
-    let mut current = map.keys().filter(|k| k.ends_with('A')).collect_vec();
-    let instr = dir.len();
+/// The steps at which a ghost stands on a node ending with `Z`.
+struct Ghost {
+    /// The steps before the ghost's walk starts repeating.
+    prefix: Vec<usize>,
+    /// The steps of the repeating part of the walk, which come back every `period` steps.
+    cycles: Vec<OffsetCycle>,
+}
+
+impl Ghost {
+    fn new(start: &String, map: &Network, dir: &[char]) -> Self {
+        // the state must include the position in the instructions to be periodic.
+        let step = |(node, i): &(&String, usize)| {
+            let next = &map[*node];
+            let node = if dir[*i] == 'L' { &next.0 } else { &next.1 };
+            (node, (i + 1) % dir.len())
+        };
+        let Cycle { prefix, length } = cycle::find_cycle((start, 0), step);
 
-    current.iter()
-        .map(|c| {
-            let mut c_i = c.clone();
-            let mut i = 0;
-            loop {
-                if c_i.ends_with('Z') { break; }
-                let next = map.get(c_i).unwrap();
-                c_i = match dir[i % instr] {
-                    'L' => &next.0,
-                    _ => &next.1,
-                };
-                i += 1;
+        let mut ghost = Self { prefix: vec![], cycles: vec![] };
+        let mut state = (start, 0);
+        for t in 0..prefix + length {
+            if state.0.ends_with('Z') {
+                if t < prefix {
+                    ghost.prefix.push(t);
+                } else {
+                    ghost.cycles.push(OffsetCycle::new(t as i128, length as i128));
+                }
             }
-            i
-        })
-        .reduce(|s1, s2| num_integer::lcm(s1, s2))
+            state = step(&state);
+        }
+        ghost
+    }
+
+    fn is_on_z(&self, t: usize) -> bool {
+        self.prefix.contains(&t) || self.cycles.iter().any(|c| c.contains(t as i128))
+    }
+}
+
+pub fn part_two(input: &str) -> Result<usize, Error> {
+    let (dir, map) = parse_input(input)?;
+    let ghosts = map.keys()
+        .filter(|k| k.ends_with('A'))
+        .map(|start| Ghost::new(start, &map, &dir))
+        .collect_vec();
+
+    // either every ghost is in its repeating part, or one of them is still in its prefix.
+    let in_cycles = ghosts.iter()
+        .map(|g| g.cycles.iter())
+        .multi_cartesian_product()
+        .filter_map(|cycles| math::first_common_step(&cycles.into_iter().copied().collect_vec()))
+        .map(|t| t as usize);
+    let in_prefix = ghosts.iter()
+        .flat_map(|g| g.prefix.iter().copied())
+        .filter(|t| ghosts.iter().all(|g| g.is_on_z(*t)));
+
+    in_cycles.chain(in_prefix)
+        .min()
+        .ok_or(Error::NoSolution)
 }
 
 #[cfg(test)]
@@ -72,7 +155,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6));
+        assert_eq!(result, Ok(6));
     }
 
     #[test]
@@ -87,6 +170,43 @@ mod tests {
 22C = (22Z, 22Z)
 22Z = (22B, 22B)
 XXX = (XXX, XXX)");
-        assert_eq!(result, Some(6));
+        assert_eq!(result, Ok(6));
+    }
+
+    #[test]
+    fn test_part_two_with_offsets() {
+        // the first ghost is on `11Z` after 1, 3, 5... steps, the second after 2, 4, 6... steps.
+        let result = part_two("L
+
+11A = (11Z, XXX)
+11Z = (11A, XXX)
+22A = (22B, XXX)
+22B = (22Z, XXX)
+22Z = (22B, XXX)
+XXX = (XXX, XXX)");
+        assert_eq!(result, Err(Error::NoSolution));
+
+        // the first ghost is on `11Z` only once, after 3 steps.
+        let result = part_two("L
+
+11A = (11B, XXX)
+11B = (11C, XXX)
+11C = (11Z, XXX)
+11Z = (11D, XXX)
+11D = (11D, XXX)
+22A = (22Z, XXX)
+22Z = (22A, XXX)
+XXX = (XXX, XXX)");
+        assert_eq!(result, Ok(3));
+    }
+
+    #[test]
+    fn test_undefined_nodes() {
+        let result = part_two("L\n\n11A = (11Z, XXX)\n11Z = (11A, 11B)\nXXX = (XXX, XXX)");
+        assert_eq!(result, Err(Error::UndefinedNode("11B".into())));
+        let result = part_one("L\n\nBBB = (BBB, BBB)");
+        assert_eq!(result, Err(Error::UndefinedNode("AAA".into())));
+        let result = part_one("LRX\n\nAAA = (AAA, AAA)");
+        assert_eq!(result, Err(Error::Parse(ParseError::new(1, 3, "expecting `L` or `R`"))));
     }
 }
@@ -1,39 +1,56 @@
-use itertools::Itertools;
+use std::fmt::Display;
+
+use advent_of_code::parse;
+use advent_of_code::polynomial::{self, ExtrapolateError};
+use advent_of_code::ParseError;
 
 advent_of_code::solution!(9);
 
-pub fn part_one(input: &str) -> Option<i32> {
-    input.lines()
-        .map(|l| l.split_ascii_whitespace().map(|x| x.parse::<i32>().unwrap()).collect_vec())
-        .map(|mut seq| {
-            let mut res = 0;
-            while !seq.iter().all(|i| *i == 0) {
-                res += *seq.last().unwrap();
-                seq = seq.windows(2).map(|l| match l {
-                    &[i, j] => j - i,
-                    _ => panic!(),
-                }).collect_vec();
-            }
-            res
-        })
-        .sum1()
-}
-
-pub fn part_two(input: &str) -> Option<i32> {
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    Parse(ParseError),
+    Extrapolate(ExtrapolateError),
+}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Self::Parse(error)
+    }
+}
+
+impl From<ExtrapolateError> for Error {
+    fn from(error: ExtrapolateError) -> Self {
+        Self::Extrapolate(error)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(error) => write!(f, "{error}"),
+            Error::Extrapolate(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+fn parse_sequences(input: &str) -> impl Iterator<Item = Result<Vec<i128>, ParseError>> + '_ {
     input.lines()
-        .map(|l| l.split_ascii_whitespace().map(|x| x.parse::<i32>().unwrap()).collect_vec())
-        .map(|mut seq| {
-            let mut first = vec![];
-            while !seq.iter().all(|i| *i == 0) {
-                first.push(*seq.first().unwrap());
-                seq = seq.windows(2).map(|l| match l {
-                    &[i, j] => j - i,
-                    _ => panic!(),
-                }).collect_vec();
-            }
-            first.iter().rev().fold(0, |c, i| *i - c)
-        })
-        .sum1()
+        .map(|l| parse::numbers(l).collect::<Result<_, _>>().map_err(|e| e.within(input, l)))
+}
+
+fn sum_extrapolated(input: &str, steps: i128) -> Result<i128, Error> {
+    parse_sequences(input).try_fold(0i128, |sum, seq| {
+        let value = polynomial::extrapolate(&seq?, steps)?;
+        Ok(sum.checked_add(value).ok_or(ExtrapolateError::Overflow)?)
+    })
+}
+
+pub fn part_one(input: &str) -> Result<i128, Error> {
+    sum_extrapolated(input, 1)
+}
+
+pub fn part_two(input: &str) -> Result<i128, Error> {
+    sum_extrapolated(input, -1)
 }
 
 #[cfg(test)]
@@ -43,12 +60,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(114));
+        assert_eq!(result, Ok(114));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
+        assert_eq!(result, Ok(2));
     }
 }
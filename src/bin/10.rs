@@ -1,177 +1,153 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
+use std::fmt::Display;
 
+use advent_of_code::geometry;
+use advent_of_code::grid::{Grid, Pos};
+use advent_of_code::point::{Dir4, Point};
+use advent_of_code::{GridCell, ParseError};
 use itertools::Itertools;
 
 advent_of_code::solution!(10);
 
-#[derive(Copy, Clone, PartialEq, Eq, Hash)]
-enum Dir { N, S, E, W }
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GridCell)]
+enum Tile {
+    #[cell('|')]
+    Vertical,
+    #[cell('-')]
+    Horizontal,
+    #[cell('L')]
+    NorthEast,
+    #[cell('J')]
+    NorthWest,
+    #[cell('7')]
+    SouthWest,
+    #[cell('F')]
+    SouthEast,
+    #[cell('.')]
+    Ground,
+    #[cell('S')]
+    Start,
+}
 
-impl Dir {
-    fn apply(&self, x: usize, y: usize) -> (usize, usize) {
-        match self {
-            Dir::N => (x, y - 1),
-            Dir::S => (x, y + 1),
-            Dir::E => (x + 1, y),
-            Dir::W => (x - 1, y),
-        }
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    Parse(ParseError),
+    /// The map has no `S` tile.
+    NoStart,
+    /// The pipes from the start tile do not lead back to it.
+    OpenLoop,
+}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Self::Parse(error)
     }
+}
 
-    fn is_possible(&self, pos: (usize, usize), map: &Vec<Vec<char>>) -> bool {
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Dir::N => pos.1 > 0 && ['|', '7', 'F'].contains(&map[pos.1 - 1][pos.0]),
-            Dir::S => pos.1 + 1 < map.len() && ['|', 'L', 'J'].contains(&map[pos.1 + 1][pos.0]),
-            Dir::E => pos.0 + 1 < map[0].len() && ['-', 'J'].contains(&map[pos.1][pos.0 + 1]),
-            Dir::W => pos.0 > 0 && ['-', 'L', 'F'].contains(&map[pos.1][pos.0 - 1]),
+            Error::Parse(error) => write!(f, "{error}"),
+            Error::NoStart => write!(f, "the map has no start tile `S`"),
+            Error::OpenLoop => write!(f, "the pipes from the start tile do not form a loop"),
         }
     }
 }
 
-fn step(pos: (usize, usize), map: &Vec<Vec<char>>, dir: &Dir) -> Option<Dir> {
-    match (map[pos.1][pos.0], dir) {
-        ('|', Dir::N) => Some(Dir::N),
-        ('|', Dir::S) => Some(Dir::S),
-        ('-', Dir::E) => Some(Dir::E),
-        ('-', Dir::W) => Some(Dir::W),
-        ('L', Dir::S) => Some(Dir::E),
-        ('L', Dir::W) => Some(Dir::N),
-        ('J', Dir::S) => Some(Dir::W),
-        ('J', Dir::E) => Some(Dir::N),
-        ('7', Dir::N) => Some(Dir::W),
-        ('7', Dir::E) => Some(Dir::S),
-        ('F', Dir::N) => Some(Dir::E),
-        ('F', Dir::W) => Some(Dir::S),
+impl Tile {
+    fn is_corner(self) -> bool {
+        matches!(self, Tile::NorthEast | Tile::NorthWest | Tile::SouthWest | Tile::SouthEast)
+    }
+}
+
+fn is_possible(dir: Dir4, pos: Pos, map: &Grid<Tile>) -> bool {
+    let Some(next) = map.step(pos, dir) else { return false };
+    step(next, map, &dir).is_some()
+}
+
+fn step(pos: Pos, map: &Grid<Tile>, dir: &Dir4) -> Option<Dir4> {
+    match (map[pos], dir) {
+        (Tile::Vertical, Dir4::Up) => Some(Dir4::Up),
+        (Tile::Vertical, Dir4::Down) => Some(Dir4::Down),
+        (Tile::Horizontal, Dir4::Right) => Some(Dir4::Right),
+        (Tile::Horizontal, Dir4::Left) => Some(Dir4::Left),
+        (Tile::NorthEast, Dir4::Down) => Some(Dir4::Right),
+        (Tile::NorthEast, Dir4::Left) => Some(Dir4::Up),
+        (Tile::NorthWest, Dir4::Down) => Some(Dir4::Left),
+        (Tile::NorthWest, Dir4::Right) => Some(Dir4::Up),
+        (Tile::SouthWest, Dir4::Up) => Some(Dir4::Left),
+        (Tile::SouthWest, Dir4::Right) => Some(Dir4::Down),
+        (Tile::SouthEast, Dir4::Up) => Some(Dir4::Right),
+        (Tile::SouthEast, Dir4::Left) => Some(Dir4::Down),
         _ => None,
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let map = input.lines()
-        .map(|l| l.chars().collect_vec())
-        .collect_vec()
-    ;
-    let (x, y) = find_start(&map);
-    let start_dir = [Dir::S, Dir::E, Dir::N, Dir::W].iter()
-        .filter(|d| d.is_possible((x, y), &map))
+fn parse_input(input: &str) -> Result<(Grid<Tile>, Pos), Error> {
+    let map = Grid::parse_cells(input)?;
+    let start = map.find(|t| *t == Tile::Start).ok_or(Error::NoStart)?;
+    Ok((map, start))
+}
+
+pub fn part_one(input: &str) -> Result<u32, Error> {
+    let (map, start) = parse_input(input)?;
+    let start_dir = [Dir4::Down, Dir4::Right, Dir4::Up, Dir4::Left].iter()
+        .filter(|d| is_possible(**d, start, &map))
         .collect_vec()
     ;
+    if start_dir.is_empty() {
+        return Err(Error::OpenLoop);
+    }
 
     let mut distances = HashMap::new();
-    distances.insert((x, y), 0);
+    distances.insert(start, 0);
     for dir in start_dir.iter() {
         let mut next_dir = Some(**dir);
-        let mut pos = (x, y);
+        let mut pos = start;
         let mut i = 0;
-        while next_dir.is_some() {
+        while let Some(d) = next_dir {
             i += 1;
-            pos = next_dir.as_ref().unwrap().apply(pos.0, pos.1);
+            pos = map.step(pos, d).ok_or(Error::OpenLoop)?;
             if let Some(i_0) = distances.get(&pos) {
                 distances.insert(pos, i.min(*i_0));
             } else {
                 distances.insert(pos, i);
             }
-            next_dir = step(pos, &map, &next_dir.unwrap());
+            next_dir = step(pos, &map, &d);
         }
-    }
-
-    Some(*distances.values().max().unwrap())
-}
-
-fn find_start(map: &Vec<Vec<char>>) -> (usize, usize) {
-    for y in 0..map.len() {
-        for x in 0..map[y].len() {
-            if map[y][x] == 'S' {
-                return (x, y);
-            }
+        if pos != start {
+            return Err(Error::OpenLoop);
         }
     }
-    panic!("no start")
+
+    distances.into_values().max().ok_or(Error::OpenLoop)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let map = input.lines()
-        .map(|l| l.chars().collect_vec())
-        .collect_vec()
-    ;
+pub fn part_two(input: &str) -> Result<usize, Error> {
+    let (map, start) = parse_input(input)?;
 
     // Determine start dir
-    let (x, y) = find_start(&map);
-    let start_dir = [Dir::S, Dir::E, Dir::N, Dir::W].iter()
-        .find(|d| d.is_possible((x, y), &map))
-        .unwrap()
+    let start_dir = [Dir4::Down, Dir4::Right, Dir4::Up, Dir4::Left].iter()
+        .find(|d| is_possible(**d, start, &map))
+        .ok_or(Error::OpenLoop)?
     ;
 
-    // Compute loop
-    let mut loop_tiles = HashSet::new();
-    let mut loop_dir = HashMap::new();
+    // Follow the loop, keeping its corners as the vertices of a polygon.
+    let mut vertices = vec![Point::from(start)];
     let mut dir = Some(*start_dir);
-    let mut turns = 0;
-    let mut pos = (x, y);
+    let mut pos = start;
     while let Some(d) = dir {
-        pos = d.apply(pos.0, pos.1);
-        loop_tiles.insert(pos);
-        loop_dir.insert(pos, d);
-        turns += match (map[pos.1][pos.0], d) {
-            ('L', Dir::W) => 1,
-            ('L', Dir::S) => -1,
-            ('J', Dir::S) => 1,
-            ('J', Dir::E) => -1,
-            ('7', Dir::E) => 1,
-            ('7', Dir::N) => -1,
-            ('F', Dir::N) => 1,
-            ('F', Dir::W) => -1,
-            _ => 0,
-        };
-        dir = step(pos, &map, &dir.unwrap());
-    }
-
-    // We enclose left tiles if there are more left turns
-    let left = turns < 0;
-
-    // Fill with water
-    let mut contained: HashSet<(usize, usize)> = HashSet::new();
-    loop_tiles.iter()
-        .flat_map(|&(x, y)| match (map[y][x], loop_dir.get(&(x, y)).unwrap(), left) {
-            ('-', Dir::E, true) | ('-', Dir::W, false) => vec![Dir::N.apply(x, y)],
-            ('-', Dir::W, true) | ('-', Dir::E, false)  => vec![Dir::S.apply(x, y)],
-            ('|', Dir::N, true) | ('|', Dir::S, false) => vec![Dir::W.apply(x, y)],
-            ('|', Dir::S, true) | ('|', Dir::N, false) => vec![Dir::E.apply(x, y)],
-            ('L', Dir::W, true) => vec![Dir::W.apply(x, y), Dir::S.apply(x, y)],
-            ('L', Dir::S, false) => vec![Dir::W.apply(x, y), Dir::S.apply(x, y)],
-            ('J', Dir::S, true) => vec![Dir::E.apply(x, y), Dir::S.apply(x, y)],
-            ('J', Dir::E, false) => vec![Dir::E.apply(x, y), Dir::S.apply(x, y)],
-            ('7', Dir::E, true) => vec![Dir::N.apply(x, y), Dir::E.apply(x, y)],
-            ('7', Dir::N, false) => vec![Dir::N.apply(x, y), Dir::E.apply(x, y)],
-            ('F', Dir::N, true) => vec![Dir::W.apply(x, y), Dir::N.apply(x, y)],
-            ('F', Dir::W, false) => vec![Dir::W.apply(x, y), Dir::N.apply(x, y)],
-            _ => vec![],
-        })
-        .for_each(|(x, y)| {
-            if !loop_tiles.contains(&(x, y)) { contained.insert((x, y)); }
-        })
-    ;
-
-    let mut changed = true;
-    while changed {
-        changed = false;
-        let mut new = HashSet::new();
-        for tile in &contained {
-            for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
-                let (x, y) = (tile.0 as isize + dx, tile.1 as isize + dy);
-                if x < 0 || y < 0 { continue }
-                let (x, y) = (x as usize, y as usize);
-                if x < map[0].len() && y < map.len() {
-                    if !contained.contains(&(x, y)) && !loop_tiles.contains(&(x, y)) {
-                        changed = true;
-                        new.insert((x, y));
-                    }
-                }
-            }
+        pos = map.step(pos, d).ok_or(Error::OpenLoop)?;
+        if map[pos].is_corner() {
+            vertices.push(Point::from(pos));
         }
-        new.iter().for_each(|t| { contained.insert(*t); });
+        dir = step(pos, &map, &d);
+    }
+    if pos != start {
+        return Err(Error::OpenLoop);
     }
 
-    Some(contained.len())
+    geometry::interior_points(&vertices).ok_or(Error::OpenLoop)
 }
 
 #[cfg(test)]
@@ -181,7 +157,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4));
+        assert_eq!(result, Ok(4));
     }
 
     #[test]
@@ -196,6 +172,20 @@ L---JF-JLJ.||-FJLJJ7
 7-L-JL7||F7|L7F-7F7|
 L.L7LFJ|||||FJL7||LJ
 L7JLJL-JLJLJL--JLJ.L");
-        assert_eq!(result, Some(10));
+        assert_eq!(result, Ok(10));
+    }
+
+    #[test]
+    fn test_no_start() {
+        assert_eq!(part_one("F7\nLJ"), Err(Error::NoStart));
+    }
+
+    #[test]
+    fn test_open_loop() {
+        // a dead end, pipes that leave the map, and a start that no pipe connects to.
+        for input in ["S-7\n|.|\nL-.", "S-7\n|.|\nL--", "S..\n...\n..."] {
+            assert_eq!(part_one(input), Err(Error::OpenLoop));
+            assert_eq!(part_two(input), Err(Error::OpenLoop));
+        }
     }
 }
@@ -1,12 +1,14 @@
 use std::collections::HashMap;
 
+use advent_of_code::fuzz::Rng;
+use advent_of_code::param::param;
 use itertools::Itertools;
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(11, part_one => [pairs: part_one, sorted: part_one_sorted], part_two => [pairs: part_two, sorted: part_two_sorted], generate);
 
 fn expand(n: isize, galaxies: &mut Vec<(isize, isize)>) {
     // Expand X
-    let x_max = galaxies.iter().map(|(x, _)| x).max().unwrap();
+    let Some(x_max) = galaxies.iter().map(|(x, _)| x).max() else { return };
     let x_e = (0..*x_max)
         .filter(|x_0| galaxies.iter().find(|(x, _)| *x == *x_0).is_none())
         .collect_vec();
@@ -16,7 +18,7 @@ fn expand(n: isize, galaxies: &mut Vec<(isize, isize)>) {
         .for_each(|x_0| galaxies.iter_mut().for_each(|(x, _)| { if *x > *x_0 { *x += n; } }));
 
     // Expand Y
-    let y_max = galaxies.iter().map(|(_, y)| y).max().unwrap();
+    let Some(y_max) = galaxies.iter().map(|(_, y)| y).max() else { return };
     let y_e = (0..*y_max)
         .filter(|y_0| galaxies.iter().find(|(_, y)| *y == *y_0).is_none())
         .collect_vec();
@@ -62,13 +64,48 @@ fn compute_distances(galaxies: &Vec<(isize, isize)>) -> Option<isize> {
 
 pub fn part_two(input: &str) -> Option<isize> {
     let mut galaxies = parse_input(input);
-    expand(999999, &mut galaxies);
+    // each empty row or column is replaced by `expansion` of them.
+    expand(param("expansion", 1_000_000) - 1, &mut galaxies);
     compute_distances(&mut galaxies)
 }
 
+pub fn part_one_sorted(input: &str) -> Option<isize> {
+    let mut galaxies = parse_input(input);
+    expand(1, &mut galaxies);
+    sum_distances_sorted(&galaxies)
+}
+
+pub fn part_two_sorted(input: &str) -> Option<isize> {
+    let mut galaxies = parse_input(input);
+    expand(param("expansion", 1_000_000) - 1, &mut galaxies);
+    sum_distances_sorted(&galaxies)
+}
+
+/// Sums the distances axis by axis: once sorted, the i-th coordinate is added for the `i`
+/// galaxies before it and subtracted for the `n - 1 - i` after it.
+fn sum_distances_sorted(galaxies: &[(isize, isize)]) -> Option<isize> {
+    if galaxies.len() < 2 { return None }
+    let n = galaxies.len() as isize;
+    let axis_sum = |mut values: Vec<isize>| {
+        values.sort_unstable();
+        values.iter().enumerate().map(|(i, v)| v * (2 * i as isize - n + 1)).sum::<isize>()
+    };
+    Some(axis_sum(galaxies.iter().map(|g| g.0).collect()) + axis_sum(galaxies.iter().map(|g| g.1).collect()))
+}
+
+/// Generates a square image of `size` rows, with a galaxy in about one cell out of six.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    (0..size)
+        .map(|_| (0..size).map(|_| if rng.chance(1.0 / 6.0) { '#' } else { '.' }).collect::<String>())
+        .join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::fuzz::check_variants;
+    use advent_of_code::param::with_params;
 
     #[test]
     fn test_part_one() {
@@ -81,4 +118,24 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(82000210));
     }
+
+    #[test]
+    fn test_sorted_variants() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        assert_eq!(part_one_sorted(&input), Some(374));
+        assert_eq!(with_params(&[("expansion", "100")], || part_two_sorted(&input)), Some(8410));
+    }
+
+    #[test]
+    fn test_variants_agree() {
+        check_variants(&[("pairs", &part_one), ("sorted", &part_one_sorted)], generate, 200);
+        check_variants(&[("pairs", &part_two), ("sorted", &part_two_sorted)], generate, 200);
+    }
+
+    #[test]
+    fn test_part_two_smaller_expansions() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        assert_eq!(with_params(&[("expansion", "10")], || part_two(&input)), Some(1030));
+        assert_eq!(with_params(&[("expansion", "100")], || part_two(&input)), Some(8410));
+    }
 }
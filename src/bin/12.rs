@@ -1,72 +1,53 @@
 use std::iter;
 
 use itertools::Itertools;
-use memoize::memoize;
+
+use advent_of_code::memo::memoize;
 
 advent_of_code::solution!(12);
 
+fn parse_line(line: &str) -> (&str, Vec<usize>) {
+    let (data, count) = line.split_once(" ").unwrap();
+    (data, count.split(",").map(|p| p.parse::<usize>().unwrap()).collect_vec())
+}
+
 pub fn part_one(input: &str) -> Option<usize> {
     input.lines()
-        .map(|l| {
-            let (data, count) = l.split_once(" ").unwrap();
-            (data, count.split(",").map(|p| p.parse::<usize>().unwrap()).collect_vec())
-        })
-        .map(|(data, groups)| {
-            count(groups.clone(), data.chars().collect_vec())
-        })
+        .map(parse_line)
+        .map(|(data, groups)| count(data.as_bytes(), &groups))
         .sum1()
 }
 
-#[memoize]
-fn count(groups: Vec<usize>, input: Vec<char>) -> usize {
-    let springs_sum = groups.iter().sum::<usize>();
-    if springs_sum == 0 { return 0; }
+/// Counts the arrangements of damaged springs matching `groups`. The memo is keyed by the
+/// offsets of the remaining springs and groups, so the recursion never copies the input.
+fn count(springs: &[u8], groups: &[usize]) -> usize {
+    memoize((0, 0), |memo, (s, g): (usize, usize)| {
+        let Some(&l) = groups.get(g) else {
+            // every group is placed, the remaining springs must all be operational.
+            return usize::from(!springs[s..].contains(&b'#'));
+        };
+        if s >= springs.len() { return 0; }
 
-    let remaining_areas = input.len();
-    if springs_sum + groups.len() > remaining_areas + 1 { return 0; }
+        // leave this spring operational.
+        let mut total = 0;
+        if springs[s] != b'#' { total += memo.get((s + 1, g)); }
 
-    let l = groups[0];
-    let mut can_consume = input[0..l].iter().all(|c| *c != '.');
-    let force_consume = input[0] == '#';
+        // start the group here, it must be followed by an operational spring or the end.
+        let end = s + l;
+        let fits = end <= springs.len()
+            && !springs[s..end].contains(&b'.')
+            && springs.get(end) != Some(&b'#');
+        if fits { total += memo.get(((end + 1).min(springs.len()), g + 1)); }
 
-    // After consuming a group the next char must be '.' / end of input
-    if can_consume {
-        if groups.len() > 1 { can_consume = input[l] != '#'; }
-        else {
-            let all_consumed = remaining_areas <= l || input[l..].iter().all(|c| *c != '#');
-            let valid = if all_consumed { 1 } else { 0 };
-            return match force_consume {
-                true => valid, // 1 group left, must consume
-                false => valid + skip_and_count(groups, input), // can consume now + maybe later
-            };
-        }
-    }
-
-    return match can_consume {
-        true => match force_consume {
-            true => count(groups[1..].to_vec(), input[l+1..].to_vec()),
-            false => count(groups[1..].to_vec(), input[l+1..].to_vec()) + skip_and_count(groups, input),
-        }
-        false => match force_consume {
-            true => 0,
-            false => skip_and_count(groups, input),
-        }
-    }
-}
-
-pub fn skip_and_count(groups: Vec<usize>, mut input: Vec<char>) -> usize {
-    input.remove(0);
-    count(groups, input)
+        total
+    })
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
     input.lines()
-        .map(|l| {
-            let (data, count) = l.split_once(" ").unwrap();
-            (data, count.split(",").map(|p| p.parse::<usize>().unwrap()).collect_vec())
-        })
+        .map(parse_line)
         .map(|(data, groups)| {
-            count(groups.repeat(5), iter::repeat(data).take(5).join("?").chars().collect_vec())
+            count(iter::repeat(data).take(5).join("?").as_bytes(), &groups.repeat(5))
         })
         .sum1()
 }
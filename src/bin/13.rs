@@ -1,6 +1,8 @@
 use std::cmp::min;
 
-use itertools::Itertools;
+use advent_of_code::grid::Grid;
+use advent_of_code::parse;
+use advent_of_code::ParseError;
 
 advent_of_code::solution!(13);
 
@@ -10,41 +12,39 @@ enum Reflection {
     Horizontal(usize),
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    input.split("\n\n")
-        .map(|bloc| bloc.lines().map(|l| l.chars().collect_vec()).collect_vec())
-        .map(|bloc| find_reflection(&bloc, 0))
+fn summarize(input: &str, tolerance: usize) -> Result<usize, ParseError> {
+    parse::blocks(input)
+        .map(|bloc| Grid::parse(bloc, |c| c).map_err(|e| e.within(input, bloc)))
+        .map(|bloc| bloc.map(|bloc| find_reflection(&bloc, tolerance)))
         .map(|r| {
-            match r {
+            r.map(|r| match r {
                 Reflection::Vertical(v) => v,
                 Reflection::Horizontal(h) => h * 100,
-            }
+            })
         })
-        .sum1()
+        .sum()
+}
+
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    summarize(input, 0)
 }
 
-fn compare_vertical(input: &Vec<Vec<char>>, x1: usize, x2: usize) -> usize {
-    (0..input.len()).map(|y| match input[y][x1] == input[y][x2] {
-        true => 0,
-        false => 1,
-    }).sum()
+fn compare_vertical(input: &Grid<char>, x1: usize, x2: usize) -> usize {
+    input.column(x1).zip(input.column(x2)).filter(|(a, b)| a != b).count()
 }
 
-fn compare_horizontal(input: &Vec<Vec<char>>, y1: usize, y2: usize) -> usize {
-    (0..input[0].len()).map(|x| match input[y1][x] == input[y2][x] {
-        true => 0,
-        false => 1,
-    }).sum()
+fn compare_horizontal(input: &Grid<char>, y1: usize, y2: usize) -> usize {
+    input.row(y1).iter().zip(input.row(y2)).filter(|(a, b)| a != b).count()
 }
 
-fn find_reflection(input: &Vec<Vec<char>>, tolerance: usize) -> Reflection {
+fn find_reflection(input: &Grid<char>, tolerance: usize) -> Reflection {
     // Horizontal
-    for y in 0..input.len() - 1 {
+    for y in 0..input.height() - 1 {
         let mut diff = compare_horizontal(&input, y, y + 1);
         if diff <= tolerance {
             if diff == tolerance && y == 0 { return Reflection::Horizontal(1) }
             else {
-                (0..min(y, input.len() - y - 2))
+                (0..min(y, input.height() - y - 2))
                     .for_each(|dy| { diff += compare_horizontal(&input, y - 1 - dy, y + 2 + dy); });
                 if diff == tolerance { return Reflection::Horizontal(y + 1) }
             }
@@ -52,12 +52,12 @@ fn find_reflection(input: &Vec<Vec<char>>, tolerance: usize) -> Reflection {
     }
 
     // Vertical
-    for x in 0..input[0].len() - 1 {
+    for x in 0..input.width() - 1 {
         let mut diff = compare_vertical(&input, x, x + 1);
         if diff <= tolerance {
             if diff == tolerance && x == 0 { return Reflection::Vertical(1) }
             else {
-                (0..min(x, input[0].len() - x - 2))
+                (0..min(x, input.width() - x - 2))
                     .for_each(|dx| { diff += compare_vertical(&input, x - 1 - dx, x + 2 + dx); });
                 if diff == tolerance { return Reflection::Vertical(x + 1) }
             }
@@ -67,17 +67,8 @@ fn find_reflection(input: &Vec<Vec<char>>, tolerance: usize) -> Reflection {
     panic!("No reflection found")
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    input.split("\n\n")
-        .map(|bloc| bloc.lines().map(|l| l.chars().collect_vec()).collect_vec())
-        .map(|bloc| find_reflection(&bloc, 1))
-        .map(|r| {
-            match r {
-                Reflection::Vertical(v) => v,
-                Reflection::Horizontal(h) => h * 100,
-            }
-        })
-        .sum1()
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    summarize(input, 1)
 }
 
 #[cfg(test)]
@@ -87,12 +78,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(405));
+        assert_eq!(result, Ok(405));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(400));
+        assert_eq!(result, Ok(400));
     }
 }
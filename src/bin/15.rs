@@ -15,7 +15,6 @@ pub fn part_one(input: &str) -> Option<usize> {
 fn hash_str(input: &str) -> usize {
     let mut hash = 0;
     for c in input.chars() {
-        if c == '\n' { continue }
         hash += c as usize;
         hash *= 17;
         hash = hash % 256;
@@ -36,7 +35,7 @@ pub fn part_two(input: &str) -> Option<usize> {
             else if instr.contains("=") {
                 let (lens, fp) = instr.split_once("=").unwrap();
                 let b = hash_str(lens);
-                let fp = fp.replace("\n", "").parse::<usize>().unwrap();
+                let fp = fp.parse::<usize>().unwrap();
                 let mut replaced = false;
                 boxes[b].iter_mut().for_each(|(box_lens, box_fp)| {
                    if lens == *box_lens { *box_fp = fp; replaced = true; }
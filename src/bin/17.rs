@@ -1,140 +1,91 @@
+use std::fmt::Display;
 use std::ops::Range;
-use std::sync::RwLock;
 
-use itertools::Itertools;
-use rayon::iter::ParallelIterator;
-use rayon::prelude::IntoParallelIterator;
+use advent_of_code::fuzz::Rng;
+use advent_of_code::grid::{Grid, Pos};
+use advent_of_code::param::param;
+use advent_of_code::point::Dir4;
+use advent_of_code::search::dial;
+use advent_of_code::ParseError;
 
-advent_of_code::solution!(17);
+advent_of_code::solution!(17, part_one, part_two, generate);
 
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
-enum Dir {
-    Up,
-    Down,
-    Left,
-    Right,
+#[derive(Clone, Eq, PartialEq, Hash)]
+struct Crucible {
+    pos: Pos,
+    dir: Option<Dir4>,
+    run: usize,
 }
 
-impl Dir {
-    fn delta(&self) -> (isize, isize) {
-        match self {
-            Dir::Up => (0, -1),
-            Dir::Down => (0, 1),
-            Dir::Left => (-1, 0),
-            Dir::Right => (1, 0),
-        }
-    }
-}
-
-#[derive(Clone)]
-struct Dist {
-    up: usize, right: usize, down: usize, left: usize,
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    Parse(ParseError),
+    /// No path reaches the bottom-right block with the allowed moves.
+    NoPath,
 }
 
-impl Default for Dist {
-    fn default() -> Self {
-        Self {
-            up: usize::MAX,
-            right: usize::MAX,
-            down: usize::MAX,
-            left: usize::MAX,
-        }
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Self::Parse(error)
     }
 }
 
-impl Dist {
-    fn get(&self, dir: &Dir) -> usize {
-        match dir {
-            Dir::Up => self.up,
-            Dir::Down => self.down,
-            Dir::Left => self.left,
-            Dir::Right => self.right,
-        }
-    }
-
-    fn set(&mut self, dir: &Dir, val: usize) {
-        match dir {
-            Dir::Up => self.up = val,
-            Dir::Down => self.down = val,
-            Dir::Left => self.left = val,
-            Dir::Right => self.right = val,
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(error) => write!(f, "{error}"),
+            Error::NoPath => write!(f, "no path reaches the bottom-right block"),
         }
     }
-
-    fn min(&self) -> usize {
-        *[self.right, self.up, self.left, self.down].iter().min().unwrap()
-    }
 }
 
-fn parse_input(input: &str) -> Vec<Vec<usize>> {
-    input.lines()
-        .map(|l| l.chars()
-            .map(|n| n as usize - '0' as usize)
-            .collect_vec()
-        )
-        .collect_vec()
+fn parse_input(input: &str) -> Result<Grid<usize>, ParseError> {
+    Grid::try_parse(input, |n| n.to_digit(10).map(|n| n as usize))
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let numbers = parse_input(input);
-    calculate_min_distance(numbers, 1..4)
+pub fn part_one(input: &str) -> Result<usize, Error> {
+    let numbers = parse_input(input)?;
+    let range = param("min_straight", 1)..param("max_straight", 3) + 1;
+    calculate_min_distance(&numbers, range).ok_or(Error::NoPath)
 }
 
-fn calculate_min_distance(numbers: Vec<Vec<usize>>, range: Range<usize>) -> Option<usize> {
-    let mut distances: Vec<Vec<RwLock<Dist>>> = (0..numbers.len()).map(|_| (0..numbers[0].len()).map(|_| RwLock::new(Dist::default())).collect_vec()).collect_vec();
-    if let Ok(mut d) = distances[0][0].write() { d.up = 0; d.down = 0; d.left = 0; d.right = 0; };
-    let mut changed = RwLock::new(true);
-
-    while *changed.read().unwrap() {
-        let new_dist = distances.iter().map(|l| l.iter().map(|lock| RwLock::new(lock.read().unwrap().clone())).collect_vec()).collect_vec();
-        changed = RwLock::new(false);
-        (0..numbers.len()).into_par_iter().for_each(|y| {
-            (0..numbers[0].len()).into_par_iter().for_each(|x| {
-                let dist = &distances[y][x];
-                for dir in [Dir::Up, Dir::Down, Dir::Left, Dir::Right] {
-                    let total = dist.read().unwrap().get(&dir);
-                    if total == usize::MAX { continue }
-
-                    let mut dirs = match dir {
-                        Dir::Up | Dir::Down => [Dir::Left, Dir::Right],
-                        Dir::Left | Dir::Right => [Dir::Up, Dir::Down],
-                    };
-                    if total == 0 { dirs = [Dir::Down, Dir::Right]; }
-
-                    for amount in range.start..range.end {
-                        for d1 in dirs {
-                            let valid = match d1 {
-                                Dir::Up => y >= amount,
-                                Dir::Down => y < numbers.len() - amount,
-                                Dir::Left => x >= amount,
-                                Dir::Right => x < numbers[0].len() - amount,
-                            };
-                            if !valid { continue }
-                            let (dx, dy) = d1.delta();
-                            let x1 = (x as isize + dx * amount as isize) as usize;
-                            let y1 = (y as isize + dy * amount as isize) as usize;
-                            let d = (1..=amount).map(|j|
-                                numbers[(y as isize + dy * j as isize) as usize][(x as isize + dx * j as isize) as usize]
-                            ).sum::<usize>() + total;
-                            if distances[y1][x1].read().unwrap().get(&d1) > d {
-                                new_dist[y1][x1].write().unwrap().set(&d1, d);
-                                if !*changed.read().unwrap() { let mut c = changed.write().unwrap(); *c = true; }
-                            }
-                        }
-                    }
-                }
-            });
-        });
-        distances = new_dist;
-    }
+/// The crucible moves between `range.start` and `range.end - 1` blocks before turning.
+fn calculate_min_distance(numbers: &Grid<usize>, range: Range<usize>) -> Option<usize> {
+    let end = (numbers.width().checked_sub(1)?, numbers.height().checked_sub(1)?);
+    let start = Crucible { pos: (0, 0), dir: None, run: 0 };
+
+    let successors = |c: &Crucible| {
+        Dir4::ALL.into_iter()
+            .filter(|d| match c.dir {
+                None => true,
+                Some(dir) if dir == *d => c.run + 1 < range.end,
+                Some(dir) => dir.reverse() != *d && range.contains(&c.run),
+            })
+            .filter_map(|d| {
+                let pos = numbers.step(c.pos, d)?;
+                let run = if c.dir == Some(d) { c.run + 1 } else { 1 };
+                Some((Crucible { pos, dir: Some(d), run }, numbers[pos]))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    dial(start, successors, |c| c.pos == end && range.contains(&c.run))
+        .map(|path| path.cost)
+}
 
-    let shortest_dist = Some(distances.last().unwrap().last().unwrap().read().unwrap().min());
-    shortest_dist
+pub fn part_two(input: &str) -> Result<usize, Error> {
+    let numbers = parse_input(input)?;
+    let range = param("min_straight", 4)..param("max_straight", 10) + 1;
+    calculate_min_distance(&numbers, range).ok_or(Error::NoPath)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let numbers = parse_input(input);
-    calculate_min_distance(numbers, 4..11)
+/// Generates a square map of `size` blocks of random heat loss.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let rows: Vec<String> = (0..size)
+        .map(|_| (0..size).map(|_| char::from(b'1' + rng.below(9) as u8)).collect())
+        .collect();
+    rows.join("\n")
 }
 
 #[cfg(test)]
@@ -144,12 +95,18 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(102));
+        assert_eq!(result, Ok(102));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(94));
+        assert_eq!(result, Ok(94));
+    }
+
+    #[test]
+    fn test_no_path() {
+        assert_eq!(part_two("12\n34"), Err(Error::NoPath));
+        assert_eq!(part_one(""), Err(Error::NoPath));
     }
 }
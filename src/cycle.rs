@@ -0,0 +1,105 @@
+//! Fast-forwarding of simulations that end up repeating themselves.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A cycle in the sequence of states of a simulation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The number of steps before the first state of the cycle.
+    pub prefix: usize,
+    /// The number of steps after which the states repeat.
+    pub length: usize,
+}
+
+/// The state of a simulation after a given number of steps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Simulation<S> {
+    pub state: S,
+    /// The cycle that was found on the way, if the simulation repeated itself before the end.
+    pub cycle: Option<Cycle>,
+}
+
+/// Returns the state after applying `step` `n` times to `initial`.
+///
+/// Full states are remembered, so as soon as one repeats the remaining steps are skipped.
+/// Two different states are never mistaken for each other, even if they share a score.
+///
+/// ```
+/// # use advent_of_code::cycle::{run, Cycle};
+/// // 3, 6, 2, 4, 8, 6, 2, ...
+/// let simulation = run(3, |n| n * 2 % 10, 1_000_000_000);
+/// assert_eq!(simulation.cycle, Some(Cycle { prefix: 1, length: 4 }));
+/// assert_eq!(simulation.state, 8);
+/// ```
+pub fn run<S>(initial: S, mut step: impl FnMut(&S) -> S, n: usize) -> Simulation<S>
+where
+    S: Clone + Eq + Hash,
+{
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut states: Vec<S> = vec![];
+    let mut state = initial;
+
+    for i in 0..n {
+        if let Some(&prefix) = seen.get(&state) {
+            let length = i - prefix;
+            return Simulation {
+                state: states.swap_remove(prefix + (n - prefix) % length),
+                cycle: Some(Cycle { prefix, length }),
+            };
+        }
+
+        let next = step(&state);
+        seen.insert(state.clone(), i);
+        states.push(state);
+        state = next;
+    }
+
+    Simulation { state, cycle: None }
+}
+
+/// Applies `step` to `initial` until a state repeats, and returns the cycle.
+/// The simulation must have a finite number of states, or this never returns.
+pub fn find_cycle<S>(initial: S, step: impl FnMut(&S) -> S) -> Cycle
+where
+    S: Clone + Eq + Hash,
+{
+    // NOTE: a finite simulation repeats itself before running out of steps.
+    run(initial, step, usize::MAX).cycle.unwrap()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{find_cycle, run, Cycle};
+
+    #[test]
+    fn matches_naive_simulation() {
+        let step = |n: &u64| (n * n + 1) % 1009;
+        for steps in [0, 1, 10, 100, 1000, 5000] {
+            let naive = (0..steps).fold(2, |n, _| step(&n));
+            assert_eq!(run(2, step, steps).state, naive);
+        }
+    }
+
+    #[test]
+    fn no_cycle_before_the_end() {
+        let simulation = run(0, |n| n + 1, 10);
+        assert_eq!(simulation.state, 10);
+        assert_eq!(simulation.cycle, None);
+    }
+
+    #[test]
+    fn distinguishes_states_with_same_score() {
+        // the first field repeats with a period of 2, the state with a period of 6.
+        let step = |(a, b): &(u8, u8)| ((a + 1) % 2, (b + 1) % 3);
+        assert_eq!(
+            find_cycle((0, 0), step),
+            Cycle {
+                prefix: 0,
+                length: 6
+            }
+        );
+    }
+}
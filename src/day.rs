@@ -80,6 +80,115 @@ impl Display for DayFromStrError {
 
 /* -------------------------------------------------------------------------- */
 
+/// A set of days of advent.
+///
+/// # Parsing
+/// A [`DaySet`] parses from a comma-separated list of days and inclusive ranges.
+///
+/// ```
+/// # use advent_of_code::{day, DaySet};
+/// let days: DaySet = "1-5,7,12".parse().unwrap();
+/// assert!(days.contains(day!(3)));
+/// assert!(!days.contains(day!(6)));
+/// assert_eq!(days.iter().count(), 7);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct DaySet(u32);
+
+impl DaySet {
+    /// Creates an empty [`DaySet`].
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Creates a [`DaySet`] containing every day of advent.
+    pub const fn all() -> Self {
+        Self(((1 << 25) - 1) << 1)
+    }
+
+    /// Creates a [`DaySet`] containing every day from `day` to the 25th.
+    pub fn since(day: Day) -> Self {
+        Self(Self::all().0 & !((1 << day.0) - 1))
+    }
+
+    /// Adds a [`Day`] to the set.
+    pub fn insert(&mut self, day: Day) {
+        self.0 |= 1 << day.0;
+    }
+
+    /// Removes a [`Day`] from the set.
+    pub fn remove(&mut self, day: Day) {
+        self.0 &= !(1 << day.0);
+    }
+
+    /// Returns `true` if the set contains the [`Day`].
+    pub fn contains(&self, day: Day) -> bool {
+        self.0 & (1 << day.0) != 0
+    }
+
+    /// Returns `true` if the set contains no days.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns the days that are in both sets.
+    #[must_use]
+    pub fn intersection(&self, other: DaySet) -> DaySet {
+        Self(self.0 & other.0)
+    }
+
+    /// Returns an iterator over the days of the set, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Day> + '_ {
+        all_days().filter(|day| self.contains(*day))
+    }
+}
+
+impl FromIterator<Day> for DaySet {
+    fn from_iter<T: IntoIterator<Item = Day>>(iter: T) -> Self {
+        let mut set = Self::empty();
+        iter.into_iter().for_each(|day| set.insert(day));
+        set
+    }
+}
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut set = Self::empty();
+
+        for item in s.split(',').map(str::trim) {
+            match item.split_once('-') {
+                Some((start, end)) => {
+                    let start: Day = start.trim().parse().map_err(|_| DaySetFromStrError)?;
+                    let end: Day = end.trim().parse().map_err(|_| DaySetFromStrError)?;
+                    if start > end {
+                        return Err(DaySetFromStrError);
+                    }
+                    (start.0..=end.0).for_each(|day| set.insert(Day(day)));
+                }
+                None => set.insert(item.parse().map_err(|_| DaySetFromStrError)?),
+            }
+        }
+
+        Ok(set)
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySet`].
+#[derive(Debug)]
+pub struct DaySetFromStrError;
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a comma-separated list of days or ranges, e.g. `1-5,7,12`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// An iterator that yields every day of advent from the 1st to the 25th.
 pub fn all_days() -> AllDays {
     AllDays::new()
@@ -134,7 +243,7 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, Day, DaySet};
 
     #[test]
     fn all_days_iterator() {
@@ -167,6 +276,30 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn day_set_from_str() {
+        let set: DaySet = "1-3, 7,12-12".parse().unwrap();
+        let days: Vec<u8> = set.iter().map(Day::into_inner).collect();
+        assert_eq!(days, vec![1, 2, 3, 7, 12]);
+    }
+
+    #[test]
+    fn day_set_from_str_errors() {
+        assert!("".parse::<DaySet>().is_err());
+        assert!("0-3".parse::<DaySet>().is_err());
+        assert!("5-3".parse::<DaySet>().is_err());
+        assert!("1,26".parse::<DaySet>().is_err());
+        assert!("1-".parse::<DaySet>().is_err());
+    }
+
+    #[test]
+    fn day_set_since() {
+        let set = DaySet::since(Day(23));
+        let days: Vec<u8> = set.iter().map(Day::into_inner).collect();
+        assert_eq!(days, vec![23, 24, 25]);
+        assert_eq!(DaySet::since(Day(1)), DaySet::all());
+    }
 }
 
 /* -------------------------------------------------------------------------- */
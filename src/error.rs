@@ -0,0 +1,112 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// An error which can be returned when parsing a puzzle input.
+///
+/// It points to the line and column (both starting at 1) of the bad input.
+///
+/// ```
+/// # use advent_of_code::ParseError;
+/// let input = "Game 1: 3 blue\nGame 2: 4 purple";
+/// let purple = input.find("purple").unwrap();
+/// let err = ParseError::at(input, &input[purple..], "unknown color");
+/// assert_eq!(err.to_string(), "line 2, column 11: unknown color");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Creates a [`ParseError`] at the given line and column.
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Creates a [`ParseError`] pointing to `at`, which must be a slice of `input`.
+    /// If it is not, the error points to the start of `input`.
+    pub fn at(input: &str, at: &str, message: impl Into<String>) -> Self {
+        let (line, column) = position(input, at);
+        Self::new(line, column, message)
+    }
+
+    /// Moves an error that was returned when parsing `part`, a slice of `input`,
+    /// so that it points to the same place relative to `input`.
+    #[must_use]
+    pub fn within(self, input: &str, part: &str) -> Self {
+        let (line, column) = position(input, part);
+        Self {
+            column: if self.line == 1 {
+                self.column + column - 1
+            } else {
+                self.column
+            },
+            line: self.line + line - 1,
+            message: self.message,
+        }
+    }
+}
+
+/// Returns the line and column of `at` in `input`.
+fn position(input: &str, at: &str) -> (usize, usize) {
+    let start = input.as_ptr() as usize;
+    let offset = (at.as_ptr() as usize)
+        .checked_sub(start)
+        .filter(|offset| *offset <= input.len())
+        .unwrap_or(0);
+
+    let before = &input[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+    (line, column)
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::ParseError;
+
+    #[test]
+    fn points_to_slice() {
+        let input = "abc\ndef\nghi";
+        let err = ParseError::at(input, &input[9..], "bad");
+        assert_eq!((err.line, err.column), (3, 2));
+    }
+
+    #[test]
+    fn points_to_start_if_not_a_slice() {
+        let other = String::from("def");
+        let err = ParseError::at("abc", &other, "bad");
+        assert_eq!((err.line, err.column), (1, 1));
+    }
+
+    #[test]
+    fn moves_within_enclosing_input() {
+        let input = "seeds: 1 2\n\nmap:\n1 2 x";
+        let block = &input[12..];
+        let line = &block[5..];
+        let err = ParseError::at(line, &line[4..], "bad").within(block, line);
+        assert_eq!((err.line, err.column), (2, 5));
+        let err = err.within(input, block);
+        assert_eq!((err.line, err.column), (4, 5));
+    }
+}
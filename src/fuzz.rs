@@ -0,0 +1,442 @@
+//! Differential testing of the variants of a part on generated inputs, with shrinking of the
+//! inputs they disagree on.
+
+use std::cell::Cell;
+use std::fmt::{self, Display};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+use crate::template::runner::{PartResult, Variant};
+
+/// Generates an input of the given size from a seed, see [`Rng`].
+pub type Generator = fn(usize, u64) -> String;
+
+/// The largest size of the inputs generated while fuzzing.
+const MAX_SIZE: usize = 20;
+
+/// The number of variant runs after which shrinking stops.
+const SHRINK_BUDGET: usize = 5_000;
+
+/// A small deterministic pseudo-random generator (SplitMix64), for writing input generators.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `0..n`.
+    ///
+    /// # Panics
+    ///
+    /// If `n` is 0.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "empty range");
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Returns `true` with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        (self.next_u64() >> 11) as f64 / ((1u64 << 53) as f64) < p
+    }
+
+    /// Returns one of `items`.
+    ///
+    /// # Panics
+    ///
+    /// If `items` is empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+}
+
+/// What a variant gave for an input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Answer(String),
+    /// The part returned no answer, with the reason if any.
+    Failed(Option<String>),
+    Panicked(String),
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Answer(answer) => write!(f, "{answer}"),
+            Outcome::Failed(None) => write!(f, "✖"),
+            Outcome::Failed(Some(reason)) => write!(f, "✖ {reason}"),
+            Outcome::Panicked(message) => write!(f, "panicked: {message}"),
+        }
+    }
+}
+
+/// An input on which the variants disagree, or one of them panics.
+#[derive(Debug, Clone)]
+pub struct Counterexample {
+    /// The size and seed of the generated input, or [`None`] if it is not generated.
+    pub generated: Option<(usize, u64)>,
+    /// The shrunk input.
+    pub input: String,
+    /// The outcome of each variant on the shrunk input.
+    pub outcomes: Vec<(&'static str, Outcome)>,
+}
+
+impl Display for Counterexample {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.generated {
+            Some((size, seed)) => write!(f, "input of size {size} (seed {seed})")?,
+            None => write!(f, "input")?,
+        }
+        writeln!(f, ", shrunk to:")?;
+        write!(f, "{}", self.input)?;
+        for (name, outcome) in &self.outcomes {
+            write!(f, "\n  {name}: {outcome}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs every variant on generated inputs of growing sizes, and returns the first input where
+/// they disagree or one of them panics, shrunk to a smaller input that still fails the same way.
+/// `inputs` are checked first, e.g. the example of the puzzle.
+///
+/// Panic messages of the variants are not printed, the counterexample reports them instead.
+pub fn find_counterexample<R: PartResult>(
+    variants: &[Variant<R>],
+    generate: Option<Generator>,
+    inputs: &[String],
+    iterations: usize,
+) -> Option<Counterexample> {
+    let given = inputs.iter().map(|input| (None, input.clone()));
+    let generated = generate.into_iter().flat_map(|generate| {
+        (0..iterations).map(move |i| {
+            let (size, seed) = (1 + i % MAX_SIZE, i as u64);
+            (Some((size, seed)), generate(size, seed))
+        })
+    });
+
+    given.chain(generated).find_map(|(generated, input)| {
+        let outcomes = run_all(variants, &input);
+        let kind = failure(&outcomes)?;
+        let input = shrink(variants, input, &kind);
+        let outcomes = run_all(variants, &input);
+        Some(Counterexample {
+            generated,
+            input,
+            outcomes: variants
+                .iter()
+                .map(|(name, _)| *name)
+                .zip(outcomes)
+                .collect(),
+        })
+    })
+}
+
+/// Checks that all `variants` agree on `iterations` inputs from `generate`, for use in tests.
+///
+/// # Panics
+///
+/// With the shrunk input if they don't, see [`find_counterexample`].
+pub fn check_variants<R: PartResult>(
+    variants: &[Variant<R>],
+    generate: Generator,
+    iterations: usize,
+) {
+    if let Some(counterexample) = find_counterexample(variants, Some(generate), &[], iterations) {
+        panic!("variants disagree on an {counterexample}");
+    }
+}
+
+thread_local! {
+    /// Set while the variants run on this thread, so that their panics are not printed.
+    static SILENCED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` without printing the message of its panics, which the caller gets from the unwind.
+///
+/// The panic hook is process-wide and tests run in parallel, so it is not swapped for every
+/// call: it is wrapped once, and only stays quiet on a thread while that thread runs `f`.
+fn silence_panics<T>(f: impl FnOnce() -> T) -> T {
+    static WRAP_HOOK: Once = Once::new();
+    WRAP_HOOK.call_once(|| {
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !SILENCED.with(Cell::get) {
+                previous_hook(info);
+            }
+        }));
+    });
+
+    // restores the flag even if `f` panics, so that later panics of the thread are printed.
+    struct Restore(bool);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SILENCED.with(|silenced| silenced.set(self.0));
+        }
+    }
+    let _restore = Restore(SILENCED.with(|silenced| silenced.replace(true)));
+
+    f()
+}
+
+/// A named parser of inputs, see [`check_parsers`].
+pub type ParserVariant<'a, T, E> = (&'static str, &'a dyn Fn(&str) -> Result<T, E>);
+
+/// Checks that all `parsers` parse the same value, or all fail, on `iterations` inputs from
+/// `generate`, for use in tests. Values are compared by their [`Debug`](fmt::Debug) output.
+///
+/// # Panics
+///
+/// With the shrunk input if they don't, like [`check_variants`].
+pub fn check_parsers<T: fmt::Debug, E>(
+    parsers: &[ParserVariant<T, E>],
+    generate: Generator,
+    iterations: usize,
+) {
+    let parsed: Vec<_> = parsers
+        .iter()
+        .map(|(_, parse)| move |input: &str| parse(input).ok().map(|value| format!("{value:?}")))
+        .collect();
+    let variants: Vec<Variant<Option<String>>> = parsers
+        .iter()
+        .zip(&parsed)
+        .map(|((name, _), parse)| (*name, parse as &dyn Fn(&str) -> Option<String>))
+        .collect();
+
+    if let Some(counterexample) = find_counterexample(&variants, Some(generate), &[], iterations) {
+        panic!("parsers disagree on an {counterexample}");
+    }
+}
+
+fn run_all<R: PartResult>(variants: &[Variant<R>], input: &str) -> Vec<Outcome> {
+    variants
+        .iter()
+        .map(|(_, func)| {
+            let result = silence_panics(|| {
+                panic::catch_unwind(AssertUnwindSafe(|| func(input).into_result()))
+            });
+            match result {
+                Ok(Ok(answer)) => Outcome::Answer(answer.to_string()),
+                Ok(Err(reason)) => Outcome::Failed(reason),
+                Err(payload) => Outcome::Panicked(
+                    payload
+                        .downcast_ref::<&str>()
+                        .map(|s| s.to_string())
+                        .or_else(|| payload.downcast_ref::<String>().cloned())
+                        .unwrap_or_default(),
+                ),
+            }
+        })
+        .collect()
+}
+
+/// How an input fails: which variants panic, and whether the others disagree.
+/// Returns [`None`] if the input does not fail.
+fn failure(outcomes: &[Outcome]) -> Option<(Vec<bool>, bool)> {
+    let panicked: Vec<bool> = outcomes
+        .iter()
+        .map(|o| matches!(o, Outcome::Panicked(_)))
+        .collect();
+    let mut others = outcomes
+        .iter()
+        .filter(|o| !matches!(o, Outcome::Panicked(_)));
+    let disagree = others
+        .next()
+        .is_some_and(|first| others.any(|o| o != first));
+
+    if !disagree && !panicked.contains(&true) {
+        return None;
+    }
+    Some((panicked, disagree))
+}
+
+/// Removes lines, then columns, of `input` for as long as the variants still fail in the same way.
+/// A disagreement must keep an answer, so that it doesn't shrink to an input that no variant can
+/// parse.
+fn shrink<R: PartResult>(
+    variants: &[Variant<R>],
+    input: String,
+    kind: &(Vec<bool>, bool),
+) -> String {
+    let budget = Cell::new(SHRINK_BUDGET);
+    let still_fails = |candidate: &str| {
+        budget.set(budget.get().saturating_sub(variants.len()));
+        let outcomes = run_all(variants, candidate);
+        let has_answer = outcomes.iter().any(|o| matches!(o, Outcome::Answer(_)));
+        failure(&outcomes).as_ref() == Some(kind) && (!kind.1 || has_answer)
+    };
+
+    let mut lines: Vec<String> = input.lines().map(str::to_string).collect();
+    loop {
+        let mut progress = false;
+
+        // removes chunks of lines, from halves down to single lines.
+        let mut chunk = lines.len().div_ceil(2);
+        while chunk > 0 {
+            let mut start = 0;
+            while start < lines.len() && budget.get() > 0 {
+                let mut candidate = lines.clone();
+                candidate.drain(start..(start + chunk).min(lines.len()));
+                if !candidate.is_empty() && still_fails(&candidate.join("\n")) {
+                    lines = candidate;
+                    progress = true;
+                } else {
+                    start += chunk;
+                }
+            }
+            chunk /= 2;
+        }
+
+        // removes a column from every line, which keeps grids rectangular.
+        let width = lines.iter().map(|l| l.chars().count()).min().unwrap_or(0);
+        let mut x = 0;
+        while x < width && budget.get() > 0 {
+            let candidate: Vec<String> = lines
+                .iter()
+                .map(|l| {
+                    l.chars()
+                        .enumerate()
+                        .filter(|(i, _)| *i != x)
+                        .map(|(_, c)| c)
+                        .collect()
+                })
+                .collect();
+            if candidate.iter().all(|l| !l.is_empty()) && still_fails(&candidate.join("\n")) {
+                lines = candidate;
+                progress = true;
+                break;
+            }
+            x += 1;
+        }
+
+        if !progress || budget.get() == 0 {
+            return lines.join("\n");
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        check_parsers, check_variants, find_counterexample, silence_panics, Outcome, Rng, SILENCED,
+    };
+    use crate::template::runner::Variant;
+    use std::cell::Cell;
+    use std::panic;
+
+    fn sum(input: &str) -> Option<u32> {
+        input.lines().map(|l| l.parse::<u32>().ok()).sum()
+    }
+
+    /// Wrong as soon as a number is at least 50.
+    fn buggy_sum(input: &str) -> Option<u32> {
+        input
+            .lines()
+            .map(|l| {
+                l.parse::<u32>()
+                    .ok()
+                    .map(|n| if n >= 50 { n + 1 } else { n })
+            })
+            .sum()
+    }
+
+    fn numbers(size: usize, seed: u64) -> String {
+        let mut rng = Rng::new(seed);
+        (0..size)
+            .map(|_| rng.below(60).to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn rng_is_deterministic() {
+        let (mut a, mut b) = (Rng::new(7), Rng::new(7));
+        let rolls: Vec<usize> = (0..100).map(|_| a.below(6)).collect();
+        assert_eq!(rolls, (0..100).map(|_| b.below(6)).collect::<Vec<_>>());
+        assert!((0..6).all(|n| rolls.contains(&n)));
+        assert_ne!(Rng::new(8).next_u64(), Rng::new(7).next_u64());
+    }
+
+    #[test]
+    fn shrinks_disagreement_to_one_line() {
+        let variants: [Variant<Option<u32>>; 2] = [("sum", &sum), ("buggy", &buggy_sum)];
+        let found = find_counterexample(&variants, Some(numbers), &[], 100).unwrap();
+
+        let n: u32 = found.input.parse().unwrap();
+        assert!(n >= 50);
+        assert_eq!(
+            found.outcomes,
+            vec![
+                ("sum", Outcome::Answer(n.to_string())),
+                ("buggy", Outcome::Answer((n + 1).to_string()))
+            ]
+        );
+    }
+
+    #[test]
+    fn shrinks_panics_keeping_the_grid_rectangular() {
+        let count = |input: &str| {
+            assert!(!input.contains("#.#"), "gap");
+            Some(input.matches('#').count())
+        };
+        let variants: [Variant<Option<usize>>; 1] = [("count", &count)];
+        let input = "....\n.#.#\n....\n####".to_string();
+        let found = find_counterexample(&variants, None, &[input], 0).unwrap();
+        assert_eq!(found.input, "#.#");
+        assert_eq!(
+            found.outcomes,
+            vec![("count", Outcome::Panicked("gap".into()))]
+        );
+    }
+
+    #[test]
+    fn panics_are_only_silenced_while_running() {
+        let result = panic::catch_unwind(|| silence_panics(|| panic!("silenced")));
+        assert!(result.is_err());
+        assert!(!SILENCED.with(Cell::get));
+    }
+
+    #[test]
+    fn agreeing_variants_pass() {
+        let variants: [Variant<Option<u32>>; 2] = [("sum", &sum), ("again", &sum)];
+        check_variants(&variants, numbers, 50);
+    }
+
+    fn parse_lines(input: &str) -> Result<Vec<u32>, String> {
+        input
+            .lines()
+            .map(|l| l.parse().map_err(|_| l.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn agreeing_parsers_pass() {
+        let split = |input: &str| -> Result<Vec<u32>, String> {
+            input
+                .split('\n')
+                .map(|l| l.parse().map_err(|_| l.to_string()))
+                .collect()
+        };
+        check_parsers(&[("lines", &parse_lines), ("split", &split)], numbers, 50);
+    }
+
+    #[test]
+    #[should_panic(expected = "parsers disagree on an input")]
+    fn disagreeing_parsers_fail() {
+        let capped = |input: &str| -> Result<Vec<u32>, String> {
+            parse_lines(input).map(|numbers| numbers.into_iter().map(|n| n.min(50)).collect())
+        };
+        check_parsers(&[("lines", &parse_lines), ("capped", &capped)], numbers, 50);
+    }
+}
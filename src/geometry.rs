@@ -0,0 +1,127 @@
+//! Areas and point locations for polygons on the integer lattice, given by their vertices in order.
+
+use crate::point::Point;
+
+/// Returns twice the area of the polygon with the shoelace formula, which keeps it an integer.
+/// The vertices can be given clockwise or counterclockwise.
+pub fn double_area(vertices: &[Point]) -> usize {
+    edges(vertices)
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum::<isize>()
+        .unsigned_abs()
+}
+
+/// Returns the number of lattice points on the edges of the polygon.
+pub fn boundary_points(vertices: &[Point]) -> usize {
+    edges(vertices)
+        .map(|(a, b)| num_integer::gcd(b.x - a.x, b.y - a.y).unsigned_abs())
+        .sum()
+}
+
+/// Returns the number of lattice points strictly inside the polygon, with Pick's theorem.
+///
+/// The polygon must not cross itself, which is not checked. Returns [`None`] for fewer than three
+/// vertices, or when the boundary is too long for the area, as for a polygon that folds back onto
+/// itself.
+///
+/// ```
+/// # use advent_of_code::geometry::interior_points;
+/// # use advent_of_code::point::Point;
+/// let square = [(0, 0), (4, 0), (4, 4), (0, 4)].map(|(x, y)| Point::new(x, y));
+/// assert_eq!(interior_points(&square), Some(9));
+/// ```
+pub fn interior_points(vertices: &[Point]) -> Option<usize> {
+    if vertices.len() < 3 {
+        return None;
+    }
+    // A = I + B / 2 - 1
+    let twice_interior = (double_area(vertices) + 2).checked_sub(boundary_points(vertices))?;
+    Some(twice_interior / 2)
+}
+
+/// Returns `true` if `p` is strictly inside the polygon, by casting a ray towards the right and
+/// counting the edges it crosses. Points on the edges are not inside.
+pub fn contains(vertices: &[Point], p: Point) -> bool {
+    let mut inside = false;
+
+    for (a, b) in edges(vertices) {
+        if on_segment(a, b, p) {
+            return false;
+        }
+        if (a.y > p.y) != (b.y > p.y) {
+            // the ray crosses the edge if the crossing is right of `p`, compared without division.
+            let cross = (b.x - a.x) * (p.y - a.y) - (p.x - a.x) * (b.y - a.y);
+            if (cross > 0) == (b.y > a.y) {
+                inside = !inside;
+            }
+        }
+    }
+
+    inside
+}
+
+fn on_segment(a: Point, b: Point, p: Point) -> bool {
+    let cross = (b.x - a.x) * (p.y - a.y) - (p.x - a.x) * (b.y - a.y);
+    cross == 0
+        && p.x >= a.x.min(b.x)
+        && p.x <= a.x.max(b.x)
+        && p.y >= a.y.min(b.y)
+        && p.y <= a.y.max(b.y)
+}
+
+/// Returns the edges of the polygon, including the one that closes it.
+fn edges(vertices: &[Point]) -> impl Iterator<Item = (Point, Point)> + '_ {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(a, b)| (*a, *b))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{boundary_points, contains, double_area, interior_points};
+    use crate::point::Point;
+
+    fn polygon(vertices: &[(isize, isize)]) -> Vec<Point> {
+        vertices.iter().map(|&(x, y)| Point::new(x, y)).collect()
+    }
+
+    #[test]
+    fn triangle() {
+        let triangle = polygon(&[(0, 0), (4, 0), (0, 3)]);
+        assert_eq!(double_area(&triangle), 12);
+        assert_eq!(boundary_points(&triangle), 8);
+        assert_eq!(interior_points(&triangle), Some(3));
+    }
+
+    #[test]
+    fn pick_matches_ray_casting() {
+        // an L shape, counterclockwise, with collinear vertices.
+        let shape = polygon(&[(0, 0), (0, 3), (0, 6), (3, 6), (3, 2), (7, 2), (7, 0)]);
+        let naive = (-1..9)
+            .flat_map(|x| (-1..8).map(move |y| Point::new(x, y)))
+            .filter(|p| contains(&shape, *p))
+            .count();
+        assert_eq!(interior_points(&shape), Some(naive));
+        assert_eq!(naive, 2 * 5 + 4);
+    }
+
+    #[test]
+    fn degenerate_polygons_have_no_interior() {
+        assert_eq!(interior_points(&[]), None);
+        assert_eq!(interior_points(&polygon(&[(0, 0), (5, 0)])), None);
+        // a flat "triangle", whose edges go back and forth along a line.
+        assert_eq!(interior_points(&polygon(&[(0, 0), (5, 0), (2, 0)])), None);
+    }
+
+    #[test]
+    fn boundary_is_not_inside() {
+        let square = polygon(&[(0, 0), (2, 0), (2, 2), (0, 2)]);
+        assert!(contains(&square, Point::new(1, 1)));
+        assert!(!contains(&square, Point::new(2, 1)));
+        assert!(!contains(&square, Point::new(0, 0)));
+        assert!(!contains(&square, Point::new(3, 1)));
+    }
+}
@@ -0,0 +1,423 @@
+//! Two-dimensional grids, as found in most puzzle inputs.
+
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+use crate::point::{Dir4, Dir8, Point};
+use crate::ParseError;
+
+/// A position in a [`Grid`], as `(x, y)` with `(0, 0)` the top-left cell.
+pub type Pos = (usize, usize);
+
+/// A value that can locate a cell of a [`Grid`]: a [`Pos`] or a [`Point`].
+pub trait GridPos: Copy {
+    /// Converts the value into a [`Pos`], or [`None`] if it has negative coordinates.
+    fn to_pos(self) -> Option<Pos>;
+}
+
+impl GridPos for Pos {
+    fn to_pos(self) -> Option<Pos> {
+        Some(self)
+    }
+}
+
+impl GridPos for Point {
+    fn to_pos(self) -> Option<Pos> {
+        Some((usize::try_from(self.x).ok()?, usize::try_from(self.y).ok()?))
+    }
+}
+
+/// The error returned when a character is not one of the cells of a `#[derive(GridCell)]` enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownCell(pub char);
+
+impl Display for UnknownCell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown cell `{}`", self.0)
+    }
+}
+
+impl std::error::Error for UnknownCell {}
+
+/// A rectangular grid of cells, stored row by row in a flat buffer.
+///
+/// ```
+/// # use advent_of_code::grid::Grid;
+/// let grid = Grid::parse("#.\n.#", |c| c == '#').unwrap();
+/// assert_eq!(grid.get((1, 1)), Some(&true));
+/// assert_eq!(grid.get((2, 1)), None);
+/// assert_eq!(grid.neighbours4((0, 0)).count(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a [`Grid`] from its cells, given row by row.
+    /// Returns [`None`] if the number of cells is not `width * height`.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Option<Self> {
+        if cells.len() != width * height {
+            return None;
+        }
+        Some(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Creates a [`Grid`] where every cell is `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Parses a [`Grid`] from the lines of `input`, mapping each character to a cell.
+    /// Fails if the lines do not all have the same length.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> Result<Self, ParseError> {
+        Self::try_parse(input, |c| Some(f(c)))
+    }
+
+    /// Parses a [`Grid`] from the lines of `input`, mapping each character to a cell.
+    /// Fails if the lines do not all have the same length, or if `f` returns [`None`].
+    pub fn try_parse(
+        input: &str,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for line in input.lines() {
+            let mut line_width = 0;
+            for (i, c) in line.char_indices() {
+                let cell = f(c).ok_or_else(|| {
+                    ParseError::at(input, &line[i..], format!("unexpected character `{c}`"))
+                })?;
+                cells.push(cell);
+                line_width += 1;
+            }
+
+            if *width.get_or_insert(line_width) != line_width {
+                return Err(ParseError::at(input, line, "lines have different lengths"));
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    /// Parses a [`Grid`] from the lines of `input`, converting each character to a cell,
+    /// typically an enum with `#[derive(GridCell)]`.
+    /// Fails if the lines do not all have the same length, or on an unknown character.
+    pub fn parse_cells(input: &str) -> Result<Self, ParseError>
+    where
+        T: TryFrom<char>,
+    {
+        Self::try_parse(input, |c| T::try_from(c).ok())
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index_of(&self, pos: impl GridPos) -> Option<usize> {
+        let (x, y) = pos.to_pos()?;
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    /// Returns `true` if `pos` is a cell of the grid.
+    pub fn contains(&self, pos: impl GridPos) -> bool {
+        self.index_of(pos).is_some()
+    }
+
+    /// Returns the cell at `pos`, or [`None`] if it is out of bounds.
+    pub fn get(&self, pos: impl GridPos) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    /// Returns the cell at `pos`, or [`None`] if it is out of bounds.
+    pub fn get_mut(&mut self, pos: impl GridPos) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Returns the position `delta` away from `pos`, if it is in bounds.
+    pub fn offset(&self, pos: Pos, delta: Point) -> Option<Pos> {
+        let pos = Point::from(pos) + delta;
+        pos.to_pos().filter(|p| self.contains(*p))
+    }
+
+    /// Returns the position one step away from `pos` in direction `dir`, if it is in bounds.
+    pub fn step(&self, pos: Pos, dir: impl Into<Dir8>) -> Option<Pos> {
+        self.offset(pos, dir.into().delta())
+    }
+
+    /// Returns the positions of the orthogonal neighbours of `pos` that are in bounds,
+    /// clockwise from the top.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir4::ALL.into_iter().filter_map(move |d| self.step(pos, d))
+    }
+
+    /// Returns the positions of the orthogonal and diagonal neighbours of `pos` that are in bounds,
+    /// clockwise from the top.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir8::ALL.into_iter().filter_map(move |d| self.step(pos, d))
+    }
+
+    /// Returns the cells of row `y`.
+    ///
+    /// # Panics
+    /// Panics if `y` is out of bounds.
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} is out of bounds");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Returns the cells of column `x`, from top to bottom.
+    ///
+    /// # Panics
+    /// Panics if `x` is out of bounds.
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        assert!(x < self.width, "column {x} is out of bounds");
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    /// Returns an iterator over the rows of the grid.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        // `chunks` panics on a zero chunk size.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Returns an iterator over every position of the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.width * self.height).map(move |i| (i % width, i / width))
+    }
+
+    /// Returns an iterator over every cell of the grid with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Returns the position of the first cell, row by row, that matches `predicate`.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter().find(|(_, c)| predicate(c)).map(|(pos, _)| pos)
+    }
+
+    /// Creates a new [`Grid`] by mapping each cell.
+    #[must_use]
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Returns the grid flipped over its main diagonal: rows become columns.
+    #[must_use]
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+        Self {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+
+    /// Returns the grid rotated a quarter turn clockwise.
+    #[must_use]
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| self.column(x).rev().cloned())
+            .collect();
+        Self {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+
+    /// Returns the grid rotated a quarter turn counterclockwise.
+    #[must_use]
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .rev()
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+        Self {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+}
+
+impl<T, P: GridPos + std::fmt::Debug> Index<P> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: P) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} is out of bounds"))
+    }
+}
+
+impl<T, P: GridPos + std::fmt::Debug> IndexMut<P> for Grid<T> {
+    fn index_mut(&mut self, pos: P) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} is out of bounds"))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grid, UnknownCell};
+    use crate::point::{Dir4, Point};
+    use crate::GridCell;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, GridCell)]
+    enum Tile {
+        #[cell('.')]
+        Empty,
+        #[cell('#')]
+        Rock,
+    }
+
+    mod shadowed {
+        #![allow(dead_code, non_camel_case_types)]
+
+        type Result<T> = std::result::Result<T, ()>;
+        struct char;
+
+        /// Neither `Copy` nor `Clone`, which the derive must not need.
+        #[derive(Debug, PartialEq, crate::GridCell)]
+        pub enum Wall {
+            #[cell('#')]
+            Wall,
+        }
+    }
+
+    fn get_grid() -> Grid<char> {
+        Grid::parse("abc\ndef", |c| c).unwrap()
+    }
+
+    #[test]
+    fn parses_rows() {
+        let grid = get_grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+    }
+
+    #[test]
+    fn rejects_ragged_lines() {
+        let err = Grid::parse("abc\nde\nfgh", |c| c).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn rejects_unexpected_characters() {
+        let err = Grid::try_parse("12\n3x", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+    }
+
+    #[test]
+    fn neighbours_stay_in_bounds() {
+        let grid = get_grid();
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours8((1, 0)).count(), 5);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn indexes_with_points() {
+        let grid = get_grid();
+        assert_eq!(grid[Point::new(1, 1)], 'e');
+        assert_eq!(grid.get(Point::new(-1, 0)), None);
+        assert_eq!(grid.step((0, 0), Dir4::Left), None);
+        assert_eq!(grid.step((0, 0), Dir4::Down), Some((0, 1)));
+    }
+
+    #[test]
+    fn transposes_and_rotates() {
+        let grid = get_grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
+
+    #[test]
+    fn finds_cells() {
+        let grid = get_grid();
+        assert_eq!(grid.find(|c| *c == 'e'), Some((1, 1)));
+        assert_eq!(grid.find(|c| *c == 'z'), None);
+    }
+
+    #[test]
+    fn parses_cells() {
+        let grid = Grid::<Tile>::parse_cells(".#\n#.").unwrap();
+        assert_eq!(grid[(1, 0)], Tile::Rock);
+        assert_eq!(grid.to_string(), ".#\n#.");
+        assert_eq!(char::from(Tile::Empty), '.');
+        assert_eq!(Tile::try_from('x'), Err(UnknownCell('x')));
+
+        let err = Grid::<Tile>::parse_cells("..\n.x").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+    }
+
+    #[test]
+    fn derives_cells_despite_shadowed_names() {
+        use shadowed::Wall;
+        assert_eq!(Wall::try_from('#'), Ok(Wall::Wall));
+        assert_eq!(Wall::Wall.to_string(), "#");
+    }
+}
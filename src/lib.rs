@@ -1,4 +1,26 @@
+pub mod cycle;
 mod day;
+mod error;
+pub mod fuzz;
+pub mod geometry;
+pub mod grid;
+pub mod math;
+pub mod memo;
+pub mod ocr;
+pub mod param;
+pub mod parse;
+pub mod point;
+pub mod polynomial;
+pub mod ranges;
+pub mod scale;
+pub mod search;
 pub mod template;
+pub mod trace;
+pub mod viz;
 
+// lets the code generated by the derive macros name this crate from the inside too.
+extern crate self as advent_of_code;
+
+pub use advent_of_code_derive::{FromInput, GridCell};
 pub use day::*;
+pub use error::*;
@@ -4,7 +4,9 @@ use args::{parse, AppArguments};
 mod args {
     use std::process;
 
-    use advent_of_code::Day;
+    use advent_of_code::param::parse_param;
+    use advent_of_code::template::commands::solve;
+    use advent_of_code::{Day, DaySet};
 
     pub enum AppArguments {
         Download {
@@ -18,13 +20,14 @@ mod args {
         },
         Solve {
             day: Day,
-            release: bool,
-            time: bool,
-            submit: Option<u8>,
+            options: solve::Options,
         },
         All {
             release: bool,
             time: bool,
+            days: DaySet,
+            failed: bool,
+            variant: Option<String>,
         },
     }
 
@@ -32,10 +35,20 @@ mod args {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
-            },
+            Some("all") => {
+                let days = args.opt_value_from_str("--days")?.unwrap_or(DaySet::all());
+                let since = args
+                    .opt_value_from_str("--since")?
+                    .map_or(DaySet::all(), DaySet::since);
+
+                AppArguments::All {
+                    release: args.contains("--release"),
+                    time: args.contains("--time"),
+                    days: days.intersection(since),
+                    failed: args.contains("--failed"),
+                    variant: args.opt_value_from_str("--variant")?,
+                }
+            }
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
@@ -45,12 +58,60 @@ mod args {
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
-            },
+            Some("solve") => {
+                let mut verbosity = 0;
+                while args.contains("-v") {
+                    verbosity += 1;
+                }
+                while args.contains("-vv") {
+                    verbosity += 2;
+                }
+                let params = args.values_from_fn("--param", parse_param)?;
+                // the number of iterations is optional.
+                let fuzz = match args.opt_value_from_str("--fuzz") {
+                    Ok(iterations) => iterations.map(Some),
+                    Err(_) => args.contains("--fuzz").then_some(None),
+                };
+                let scale = args.opt_value_from_fn("--scale", parse_factors)?;
+                let submit = args.opt_value_from_str("--submit")?;
+                let part = args.opt_value_from_str("--part")?;
+
+                if part.is_some_and(|p| p != 1 && p != 2) {
+                    eprintln!("Unexpected part: expecting `--part 1` or `--part 2`.");
+                    process::exit(1);
+                }
+
+                if part.is_some() && submit.is_some() && part != submit {
+                    eprintln!(
+                        "Cannot submit a part that is not run: `--part` and `--submit` differ."
+                    );
+                    process::exit(1);
+                }
+
+                if !params.is_empty() && submit.is_some() {
+                    eprintln!(
+                        "Cannot submit an answer computed with changed constants: drop `--param`."
+                    );
+                    process::exit(1);
+                }
+
+                AppArguments::Solve {
+                    day: args.free_from_str()?,
+                    options: solve::Options {
+                        release: args.contains("--release"),
+                        time: args.contains("--time"),
+                        submit,
+                        // submitting a part only needs to run that part.
+                        part: part.or(submit),
+                        viz: args.contains("--viz"),
+                        verbosity,
+                        params,
+                        variant: args.opt_value_from_str("--variant")?,
+                        fuzz,
+                        scale,
+                    },
+                }
+            }
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -68,6 +129,16 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parses the comma-separated factors of `--scale`, e.g. `1,2,4,8`.
+    fn parse_factors(s: &str) -> Result<Vec<usize>, String> {
+        s.split(',')
+            .map(|n| match n.trim().parse() {
+                Ok(0) | Err(_) => Err(format!("expecting positive numbers, got `{n}`")),
+                Ok(n) => Ok(n),
+            })
+            .collect()
+    }
 }
 
 fn main() {
@@ -77,16 +148,17 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
+            AppArguments::All {
+                release,
+                time,
+                days,
+                failed,
+                variant,
+            } => all::handle(days, failed, release, time, variant.as_deref()),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold { day } => scaffold::handle(day),
-            AppArguments::Solve {
-                day,
-                release,
-                time,
-                submit,
-            } => solve::handle(day, release, time, submit),
+            AppArguments::Solve { day, options } => solve::handle(day, &options),
         },
     };
 }
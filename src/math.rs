@@ -0,0 +1,146 @@
+//! Number theory helpers, on `i128` so that products of two `i64` moduli never overflow.
+
+/// Returns `(g, x, y)` such that `a * x + b * y = g`, with `g` the non-negative gcd of `a` and `b`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Returns the inverse of `a` modulo `m`, in `0..m`, or [`None`] if they are not coprime.
+pub fn mod_inverse(a: i128, m: i128) -> Option<i128> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)`, given as `(residue, modulus)`.
+///
+/// The moduli do not need to be coprime. Returns `(x, lcm)` with `x` the smallest non-negative
+/// solution and `lcm` the least common multiple of the moduli, so that every solution is
+/// `x + k * lcm`. Returns [`None`] if the congruences contradict each other.
+///
+/// ```
+/// # use advent_of_code::math::crt;
+/// assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+/// assert_eq!(crt(&[(0, 4), (1, 6)]), None);
+/// ```
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    congruences
+        .iter()
+        .try_fold((0, 1), |(x, m), &(r, n)| merge(x, m, r.rem_euclid(n), n))
+}
+
+/// Merges `x ≡ a (mod m)` and `x ≡ b (mod n)` into a single congruence.
+fn merge(a: i128, m: i128, b: i128, n: i128) -> Option<(i128, i128)> {
+    let (g, p, _) = extended_gcd(m, n);
+    if (b - a) % g != 0 {
+        return None;
+    }
+    let lcm = m / g * n;
+    // a + m * k ≡ b (mod n)  <=>  k ≡ (b - a) / g * p (mod n / g)
+    let k = ((b - a) / g).rem_euclid(n / g) * p.rem_euclid(n / g) % (n / g);
+    Some(((a + m * k).rem_euclid(lcm), lcm))
+}
+
+/// An event that first happens at `offset`, then every `period` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OffsetCycle {
+    pub offset: i128,
+    pub period: i128,
+}
+
+impl OffsetCycle {
+    pub fn new(offset: i128, period: i128) -> Self {
+        Self { offset, period }
+    }
+
+    /// Returns `true` if the event happens at step `t`.
+    pub fn contains(&self, t: i128) -> bool {
+        t >= self.offset && (t - self.offset) % self.period == 0
+    }
+}
+
+/// Returns the first step at which every event of `cycles` happens, or [`None`] if they never
+/// happen together. Unlike a plain least common multiple, the cycles may start at any offset.
+///
+/// ```
+/// # use advent_of_code::math::{first_common_step, OffsetCycle};
+/// // 3, 7, 11, 15, ... and 5, 11, 17, ...
+/// let cycles = [OffsetCycle::new(3, 4), OffsetCycle::new(5, 6)];
+/// assert_eq!(first_common_step(&cycles), Some(11));
+/// ```
+pub fn first_common_step(cycles: &[OffsetCycle]) -> Option<i128> {
+    let congruences: Vec<(i128, i128)> = cycles.iter().map(|c| (c.offset, c.period)).collect();
+    let (x, lcm) = crt(&congruences)?;
+    // every event must have happened at least once.
+    let start = cycles.iter().map(|c| c.offset).max().unwrap_or(0);
+    Some(if x >= start {
+        x
+    } else {
+        x + (start - x + lcm - 1) / lcm * lcm
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{crt, extended_gcd, first_common_step, mod_inverse, OffsetCycle};
+
+    #[test]
+    fn extended_gcd_identity() {
+        for (a, b) in [(240, 46), (-12, 18), (7, 0), (0, 5), (17, 31)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(a * x + b * y, g);
+            assert_eq!(g, num_integer::gcd(a, b));
+        }
+    }
+
+    #[test]
+    fn inverses() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+    }
+
+    #[test]
+    fn crt_matches_brute_force() {
+        let moduli = [2, 3, 4, 6, 9, 10];
+        for &m in &moduli {
+            for &n in &moduli {
+                for a in 0..m {
+                    for b in 0..n {
+                        let lcm = num_integer::lcm(m, n);
+                        let naive = (0..lcm).find(|x| x % m == a && x % n == b);
+                        assert_eq!(crt(&[(a, m), (b, n)]), naive.map(|x| (x, lcm)));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn common_step_waits_for_offsets() {
+        // both cycles contain 2, but the second one only starts at 12.
+        let cycles = [OffsetCycle::new(2, 5), OffsetCycle::new(12, 10)];
+        assert_eq!(first_common_step(&cycles), Some(12));
+        assert!(cycles.iter().all(|c| c.contains(22)));
+
+        let cycles = [OffsetCycle::new(0, 2), OffsetCycle::new(1, 4)];
+        assert_eq!(first_common_step(&cycles), None);
+    }
+}
@@ -0,0 +1,106 @@
+//! Memoized recursion over cheap keys, with a cache that only lives for one computation.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The cache of a memoized recursive function, keyed by small copyable values such as indices.
+///
+/// The function receives the [`Memo`] itself, and calls [`Memo::get`] for its recursive calls.
+/// The cache is dropped with the [`Memo`], so nothing outlives the computation.
+pub struct Memo<'f, K, V> {
+    cache: HashMap<K, V>,
+    f: &'f dyn Fn(&mut Memo<'f, K, V>, K) -> V,
+}
+
+impl<'f, K, V> Memo<'f, K, V>
+where
+    K: Copy + Eq + Hash,
+    V: Clone,
+{
+    pub fn new(f: &'f dyn Fn(&mut Memo<'f, K, V>, K) -> V) -> Self {
+        Self {
+            cache: HashMap::new(),
+            f,
+        }
+    }
+
+    /// Returns the value of the function for `key`, computing it only the first time.
+    pub fn get(&mut self, key: K) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            return value.clone();
+        }
+        let f = self.f;
+        let value = f(self, key);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    /// Returns the number of values computed so far.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
+
+/// Computes `f(key)` with a fresh cache, dropped once the value is returned.
+///
+/// Borrowed data is captured by `f`, so keys can be offsets into it instead of owned copies.
+///
+/// ```
+/// # use advent_of_code::memo::memoize;
+/// let fib = memoize(90, |memo, n: u64| {
+///     if n < 2 { n } else { memo.get(n - 1) + memo.get(n - 2) }
+/// });
+/// assert_eq!(fib, 2880067194370816120);
+/// ```
+pub fn memoize<K, V>(key: K, f: impl Fn(&mut Memo<K, V>, K) -> V) -> V
+where
+    K: Copy + Eq + Hash,
+    V: Clone,
+{
+    Memo::new(&f).get(key)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{memoize, Memo};
+    use std::cell::Cell;
+
+    #[test]
+    fn computes_each_key_once() {
+        let calls = Cell::new(0);
+        let f = |memo: &mut Memo<u32, u64>, n: u32| {
+            calls.set(calls.get() + 1);
+            if n < 2 {
+                1
+            } else {
+                memo.get(n - 1) + memo.get(n - 2)
+            }
+        };
+        let mut memo = Memo::new(&f);
+        assert_eq!(memo.get(40), 165580141);
+        assert_eq!(calls.get(), 41);
+        assert_eq!(memo.len(), 41);
+    }
+
+    #[test]
+    fn keys_are_offsets_into_borrowed_data() {
+        // the number of ways to climb to the end of the stairs, with steps of 1 or 2,
+        // where broken stairs cannot be stepped on.
+        let stairs = [true, true, false, true, true, true, false, true];
+        let ways = |start: usize| {
+            memoize(start, |memo, i: usize| match i {
+                _ if i == stairs.len() => 1u64,
+                _ if i > stairs.len() || !stairs[i] => 0,
+                _ => memo.get(i + 1) + memo.get(i + 2),
+            })
+        };
+        assert_eq!(ways(2), 0);
+        assert_eq!(ways(0), 2);
+    }
+}
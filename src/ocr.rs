@@ -0,0 +1,180 @@
+//! Recognition of the letters that some puzzles draw as their answer, in the usual two fonts.
+
+use crate::grid::Grid;
+
+/// The font that is 6 cells high, with letters 4 or 5 cells wide.
+const FONT_6: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// The font that is 10 cells high, with letters 6 cells wide.
+#[rustfmt::skip]
+const FONT_10: [(char, &str); 15] = [
+    ('A', "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#"),
+    ('B', "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####."),
+    ('C', ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####."),
+    ('E', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######"),
+    ('F', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+    ('G', ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#"),
+    ('H', "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#"),
+    ('J', "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###.."),
+    ('K', "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#"),
+    ('L', "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######"),
+    ('N', "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#"),
+    ('P', "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+    ('R', "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#"),
+    ('X', "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#"),
+    ('Z', "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######"),
+];
+
+/// Reads the letters drawn by the lit cells of `grid`.
+///
+/// Letters are separated by at least one empty column, and empty rows above and below them
+/// are ignored. Returns [`None`] if the letters are not 6 or 10 cells high, or if one of them
+/// is not in the font.
+pub fn decode(grid: &Grid<bool>) -> Option<String> {
+    let lit_rows: Vec<usize> = (0..grid.height())
+        .filter(|y| grid.row(*y).contains(&true))
+        .collect();
+    let (top, bottom) = (*lit_rows.first()?, *lit_rows.last()?);
+    let font: &[(char, &str)] = match bottom - top + 1 {
+        6 => &FONT_6,
+        10 => &FONT_10,
+        _ => return None,
+    };
+
+    let is_lit = |x: usize| (top..=bottom).any(|y| grid[(x, y)]);
+    let mut letters = String::new();
+    let mut x = 0;
+    while x < grid.width() {
+        if !is_lit(x) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < grid.width() && is_lit(x) {
+            x += 1;
+        }
+
+        let glyph: Vec<String> = (top..=bottom)
+            .map(|y| {
+                grid.row(y)[start..x]
+                    .iter()
+                    .map(|lit| if *lit { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        let glyph = glyph.join("\n");
+        let (letter, _) = font.iter().find(|(_, g)| *g == glyph)?;
+        letters.push(*letter);
+    }
+
+    Some(letters)
+}
+
+/// Reads the letters drawn in `text`, where `#` and `█` are lit and any other character is not.
+/// Lines can have different lengths, e.g. if trailing spaces were trimmed.
+///
+/// ```
+/// # use advent_of_code::ocr::decode_str;
+/// let text = "\
+/// █..█.███.
+/// █..█..█..
+/// ████..█..
+/// █..█..█..
+/// █..█..█..
+/// █..█.███.";
+/// assert_eq!(decode_str(text), Some("HI".to_string()));
+/// ```
+pub fn decode_str(text: &str) -> Option<String> {
+    let lines: Vec<&str> = text.lines().collect();
+    let width = lines.iter().map(|l| l.chars().count()).max()?;
+    let cells = lines
+        .iter()
+        .flat_map(|line| {
+            let lit = line.chars().map(|c| c == '#' || c == '█');
+            lit.chain(std::iter::repeat(false)).take(width)
+        })
+        .collect();
+    decode(&Grid::new(width, lines.len(), cells)?)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decode, decode_str, FONT_10, FONT_6};
+    use crate::grid::Grid;
+
+    /// Draws `letters` side by side, with `gap` empty columns between them.
+    fn draw(font: &[(char, &str)], letters: &str, gap: usize) -> String {
+        let glyphs: Vec<Vec<&str>> = letters
+            .chars()
+            .map(|c| {
+                font.iter()
+                    .find(|(l, _)| *l == c)
+                    .unwrap()
+                    .1
+                    .lines()
+                    .collect()
+            })
+            .collect();
+        (0..glyphs[0].len())
+            .map(|y| {
+                glyphs
+                    .iter()
+                    .map(|g| g[y])
+                    .collect::<Vec<_>>()
+                    .join(&".".repeat(gap))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn decodes_every_letter() {
+        let letters: String = FONT_6.iter().map(|(c, _)| c).collect();
+        assert_eq!(decode_str(&draw(&FONT_6, &letters, 1)), Some(letters));
+
+        let letters: String = FONT_10.iter().map(|(c, _)| c).collect();
+        assert_eq!(decode_str(&draw(&FONT_10, &letters, 2)), Some(letters));
+    }
+
+    #[test]
+    fn decodes_grid_with_margins() {
+        let drawn = draw(&FONT_6, "EFL", 1);
+        let blank = ".".repeat(drawn.find('\n').unwrap() + 3);
+        let mut lines = vec![blank.clone()];
+        lines.extend(drawn.lines().map(|l| format!(".{l}..")));
+        lines.push(blank);
+        let grid = Grid::parse(&lines.join("\n"), |c| c == '#').unwrap();
+        assert_eq!(decode(&grid), Some("EFL".to_string()));
+    }
+
+    #[test]
+    fn rejects_unknown_shapes() {
+        assert_eq!(decode_str("##\n##"), None);
+        assert_eq!(
+            decode_str(&draw(&FONT_6, "HI", 1).replace("#..#.", "#.##.")),
+            None
+        );
+        assert_eq!(decode_str(""), None);
+    }
+}
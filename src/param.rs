@@ -0,0 +1,143 @@
+//! Constants of the puzzles that can be changed with `cargo solve <day> --param <name>=<value>`.
+
+use std::cell::RefCell;
+use std::env;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// The parameters given on the command line, in order.
+static ARGS: OnceLock<Vec<(String, String)>> = OnceLock::new();
+
+thread_local! {
+    /// The parameters set by [`with_params`], which take precedence over the command line.
+    static OVERRIDES: RefCell<Vec<(String, String)>> = const { RefCell::new(vec![]) };
+}
+
+/// Returns the value of the parameter `name`, or `default` if it was not given.
+/// When a parameter is given several times, the last value wins.
+///
+/// ```
+/// # use advent_of_code::param::{param, with_params};
+/// assert_eq!(param("cycles", 1_000_000_000), 1_000_000_000);
+/// assert_eq!(with_params(&[("cycles", "3")], || param("cycles", 1_000_000_000)), 3);
+/// ```
+///
+/// # Panics
+///
+/// If the value given for `name` cannot be parsed as a `T`.
+pub fn param<T>(name: &str, default: T) -> T
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let value = OVERRIDES
+        .with(|overrides| find(&overrides.borrow(), name).map(str::to_string))
+        .or_else(|| find(ARGS.get_or_init(|| from_args(env::args())), name).map(str::to_string));
+
+    match value {
+        Some(value) => value
+            .parse()
+            .unwrap_or_else(|e| panic!("invalid value `{value}` for parameter `{name}`: {e}")),
+        None => default,
+    }
+}
+
+/// Runs `f` with the given parameters, e.g. to check a solution against the smaller values used
+/// by the examples of the puzzle.
+pub fn with_params<R>(params: &[(&str, &str)], f: impl FnOnce() -> R) -> R {
+    let added: Vec<(String, String)> = params
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    let count = added.len();
+    OVERRIDES.with(|overrides| overrides.borrow_mut().extend(added));
+
+    // removes them again even if `f` panics, so that a failed test doesn't leak into the next.
+    struct Reset(usize);
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            OVERRIDES.with(|overrides| {
+                let mut overrides = overrides.borrow_mut();
+                let len = overrides.len();
+                overrides.truncate(len - self.0);
+            });
+        }
+    }
+    let _reset = Reset(count);
+
+    f()
+}
+
+/// Splits a `<name>=<value>` parameter.
+pub fn parse_param(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((name, value)) if !name.is_empty() => Ok((name.to_string(), value.to_string())),
+        _ => Err("expecting `<name>=<value>`".to_string()),
+    }
+}
+
+fn from_args(args: impl IntoIterator<Item = String>) -> Vec<(String, String)> {
+    let mut params = vec![];
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--param" {
+            if let Some(param) = args.next().and_then(|p| parse_param(&p).ok()) {
+                params.push(param);
+            }
+        }
+    }
+    params
+}
+
+fn find<'a>(params: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    params
+        .iter()
+        .rev()
+        .find(|(n, _)| n == name)
+        .map(|(_, value)| value.as_str())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{from_args, param, parse_param, with_params};
+
+    #[test]
+    fn reads_params_from_args() {
+        let args = [
+            "11",
+            "--param",
+            "expansion=10",
+            "--time",
+            "--param",
+            "a=b=c",
+        ];
+        assert_eq!(
+            from_args(args.map(String::from)),
+            vec![
+                ("expansion".to_string(), "10".to_string()),
+                ("a".to_string(), "b=c".to_string())
+            ]
+        );
+        assert!(parse_param("=1").is_err());
+        assert!(parse_param("expansion").is_err());
+    }
+
+    #[test]
+    fn nested_params_take_precedence() {
+        let values = with_params(&[("n", "1"), ("m", "2")], || {
+            let inner = with_params(&[("n", "3")], || (param("n", 0), param("m", 0)));
+            (inner, param("n", 0))
+        });
+        assert_eq!(values, ((3, 2), 1));
+        assert_eq!(param("n", 0), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid value `x` for parameter `n`")]
+    fn rejects_invalid_values() {
+        with_params(&[("n", "x")], || param::<u32>("n", 0));
+    }
+}
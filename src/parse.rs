@@ -0,0 +1,412 @@
+//! Helpers for the shapes of input that come back every year: numbers, blocks and labelled lines.
+//!
+//! Everything borrows from the input, and errors point into it with a [`ParseError`].
+
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use crate::ParseError;
+
+/// An integer type that can be found in an input with [`numbers`].
+pub trait Number: FromStr + Copy {
+    /// Whether a `-` right before the digits belongs to the number.
+    const SIGNED: bool;
+}
+
+macro_rules! number {
+    ($signed:expr => $($t:ty),*) => {
+        $(impl Number for $t {
+            const SIGNED: bool = $signed;
+        })*
+    };
+}
+
+number!(false => u8, u16, u32, u64, u128, usize);
+number!(true => i8, i16, i32, i64, i128, isize);
+
+/// Returns an iterator over every integer in `input`, ignoring whatever is around them.
+///
+/// For signed types, a `-` right before the digits makes the number negative, so `3-5` reads
+/// as `3` and `-5`. A number that does not fit in `T` is an error pointing to it. Nothing is
+/// allocated, unless there is an error.
+///
+/// ```
+/// # use advent_of_code::parse::numbers;
+/// let line = "Time: 7  15 -30";
+/// assert_eq!(numbers::<u32>(line).collect::<Result<Vec<_>, _>>()?, vec![7, 15, 30]);
+/// assert_eq!(numbers::<i32>(line).collect::<Result<Vec<_>, _>>()?, vec![7, 15, -30]);
+/// # Ok::<(), advent_of_code::ParseError>(())
+/// ```
+pub fn numbers<T: Number>(input: &str) -> Numbers<'_, T> {
+    Numbers {
+        input,
+        rest: input,
+        number: PhantomData,
+    }
+}
+
+/// The iterator returned by [`numbers`].
+#[derive(Debug, Clone)]
+pub struct Numbers<'a, T> {
+    input: &'a str,
+    rest: &'a str,
+    number: PhantomData<T>,
+}
+
+impl<T: Number> Iterator for Numbers<'_, T> {
+    type Item = Result<T, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.rest.as_bytes();
+        let digits = bytes.iter().position(u8::is_ascii_digit)?;
+        let end = bytes[digits..]
+            .iter()
+            .position(|b| !b.is_ascii_digit())
+            .map_or(bytes.len(), |len| digits + len);
+        let start = if T::SIGNED && digits > 0 && bytes[digits - 1] == b'-' {
+            digits - 1
+        } else {
+            digits
+        };
+
+        let number = &self.rest[start..end];
+        self.rest = &self.rest[end..];
+        Some(number.parse().map_err(|_| {
+            let message = format!("`{number}` does not fit in {}", std::any::type_name::<T>());
+            ParseError::at(self.input, number, message)
+        }))
+    }
+}
+
+/// Returns an iterator over the blocks of `input`, which are separated by blank lines.
+///
+/// Lines may end with `\r\n`, and several blank lines count as one separator. The blocks are
+/// slices of `input`, without their final line ending, so they can be used with
+/// [`ParseError::within`].
+///
+/// ```
+/// # use advent_of_code::parse::blocks;
+/// let input = "a\r\nb\r\n\r\nc\n\n\nd\n";
+/// assert_eq!(blocks(input).collect::<Vec<_>>(), vec!["a\r\nb", "c", "d"]);
+/// ```
+pub fn blocks(input: &str) -> impl Iterator<Item = &str> + '_ {
+    let mut rest = input;
+
+    std::iter::from_fn(move || {
+        let mut start = None;
+        let mut end = 0;
+        let mut offset = 0;
+
+        for line in rest.split_inclusive('\n') {
+            let content = line.trim_end_matches(['\n', '\r']);
+            if content.is_empty() {
+                if start.is_some() {
+                    break;
+                }
+            } else {
+                start.get_or_insert(offset);
+                end = offset + content.len();
+            }
+            offset += line.len();
+        }
+
+        let block = &rest[start?..end];
+        rest = &rest[offset..];
+        Some(block)
+    })
+}
+
+/// Parses every line of `input` into a `T`, e.g. a struct with `#[derive(FromInput)]`.
+/// Errors point into `input` rather than into the line that failed.
+pub fn lines<'a, T>(input: &'a str) -> Result<Vec<T>, ParseError>
+where
+    T: TryFrom<&'a str, Error = ParseError>,
+{
+    input
+        .lines()
+        .map(|line| T::try_from(line).map_err(|e| e.within(input, line)))
+        .collect()
+}
+
+/// Splits `input` around the first `delimiter`, or fails pointing to `input`.
+pub fn split_once<'a>(input: &'a str, delimiter: &str) -> Result<(&'a str, &'a str), ParseError> {
+    input
+        .split_once(delimiter)
+        .ok_or_else(|| ParseError::at(input, input, format!("expecting `{delimiter}`")))
+}
+
+/// Parses `input` as a number, or fails pointing to `input`.
+pub fn number<T: Number>(input: &str) -> Result<T, ParseError> {
+    input
+        .parse()
+        .map_err(|_| ParseError::at(input, input, format!("`{input}` is not a number")))
+}
+
+/// A cursor over a line of input, for lines made of labels, numbers and separators.
+///
+/// Each method consumes a piece of the line and fails with a [`ParseError`] pointing to the
+/// position in the line where the parser was.
+///
+/// ```
+/// # use advent_of_code::parse::Parser;
+/// let mut card = Parser::new("Card 1: 41 48 | 83 86  6");
+/// card.tag("Card")?;
+/// let number: u32 = card.number()?;
+/// card.tag(":")?;
+/// let winning: Vec<u32> = card.until("|")?.numbers()?;
+/// let numbers: Vec<u32> = card.numbers()?;
+/// assert_eq!((number, winning, numbers), (1, vec![41, 48], vec![83, 86, 6]));
+/// # Ok::<(), advent_of_code::ParseError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Parser<'a> {
+    input: &'a str,
+    rest: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self { input, rest: input }
+    }
+
+    /// Returns what is left to parse.
+    pub fn rest(&self) -> &'a str {
+        self.rest
+    }
+
+    /// Returns an error pointing to the current position.
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::at(self.input, self.rest, message)
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    /// Consumes `tag`, after any whitespace.
+    pub fn tag(&mut self, tag: &str) -> Result<&mut Self, ParseError> {
+        self.skip_whitespace();
+        match self.rest.strip_prefix(tag) {
+            Some(rest) => {
+                self.rest = rest;
+                Ok(self)
+            }
+            None => Err(self.error(format!("expecting `{tag}`"))),
+        }
+    }
+
+    /// Consumes a word, i.e. everything up to the next whitespace, after any whitespace.
+    pub fn word(&mut self) -> Result<&'a str, ParseError> {
+        self.skip_whitespace();
+        let len = self
+            .rest
+            .find(|c: char| c.is_whitespace())
+            .unwrap_or(self.rest.len());
+        if len == 0 {
+            return Err(self.error("expecting a word"));
+        }
+        let (word, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        Ok(word)
+    }
+
+    /// Consumes a number, after any whitespace.
+    pub fn number<T: Number>(&mut self) -> Result<T, ParseError> {
+        self.skip_whitespace();
+        let len = self
+            .rest
+            .char_indices()
+            .find(|&(i, c)| !(c.is_ascii_digit() || (T::SIGNED && i == 0 && c == '-')))
+            .map_or(self.rest.len(), |(i, _)| i);
+        let n = self.rest[..len]
+            .parse()
+            .map_err(|_| self.error("expecting a number"))?;
+        self.rest = &self.rest[len..];
+        Ok(n)
+    }
+
+    /// Consumes every whitespace-separated number up to the end.
+    pub fn numbers<T: Number>(&mut self) -> Result<Vec<T>, ParseError> {
+        let mut numbers = vec![];
+        self.skip_whitespace();
+        while !self.rest.is_empty() {
+            numbers.push(self.number()?);
+            self.skip_whitespace();
+        }
+        Ok(numbers)
+    }
+
+    /// Consumes everything up to `delimiter`, and the delimiter itself.
+    /// Returns a parser over what was before the delimiter, with errors that still point
+    /// into the whole line.
+    pub fn until(&mut self, delimiter: &str) -> Result<Parser<'a>, ParseError> {
+        let (before, after) = self
+            .rest
+            .split_once(delimiter)
+            .ok_or_else(|| self.error(format!("expecting `{delimiter}`")))?;
+        self.rest = after;
+        Ok(Parser {
+            input: self.input,
+            rest: before,
+        })
+    }
+
+    /// Fails if anything but whitespace is left.
+    pub fn end(&mut self) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        if self.rest.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("unexpected trailing input"))
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{blocks, lines, number, numbers, split_once, Parser};
+    use crate::{FromInput, ParseError};
+
+    #[derive(Debug, PartialEq, FromInput)]
+    #[input("Card {number}: {winning: ws-list} | {numbers: ws-list}")]
+    struct Card {
+        number: u32,
+        winning: Vec<u32>,
+        numbers: Vec<u32>,
+    }
+
+    #[derive(Debug, PartialEq, FromInput)]
+    #[input("{name} @ {position: comma-list}!")]
+    struct Named {
+        name: String,
+        position: Vec<i32>,
+    }
+
+    /// Derives a parser next to names that shadow the prelude, which the parser must not use.
+    mod shadowed {
+        #![allow(dead_code, non_camel_case_types, unused_macros)]
+
+        type Result<T> = std::result::Result<T, ()>;
+        struct str;
+        macro_rules! format {
+            ($($arg:tt)*) => {
+                compile_error!("the derived parser uses the `format!` in scope")
+            };
+        }
+
+        #[derive(Debug, PartialEq, crate::FromInput)]
+        #[input("{name} = {values: comma-list}")]
+        pub struct Values {
+            pub name: String,
+            pub values: Vec<u8>,
+        }
+    }
+
+    #[test]
+    fn finds_numbers() {
+        let input = "x=-12, y=3..-4 z:007";
+        assert_eq!(
+            numbers::<i64>(input).collect::<Result<Vec<_>, _>>(),
+            Ok(vec![-12, 3, -4, 7])
+        );
+        assert_eq!(
+            numbers::<u8>(input).collect::<Result<Vec<_>, _>>(),
+            Ok(vec![12, 3, 4, 7])
+        );
+        assert_eq!(numbers::<u8>("no numbers - here").next(), None);
+    }
+
+    #[test]
+    fn numbers_overflow() {
+        assert_eq!(
+            numbers::<u8>("1 2 256 3").collect::<Result<Vec<_>, _>>(),
+            Err(ParseError::new(1, 5, "`256` does not fit in u8"))
+        );
+    }
+
+    #[test]
+    fn splits_blocks() {
+        let input = "\na\nb\n\n\nc\r\n\r\nd";
+        assert_eq!(blocks(input).collect::<Vec<_>>(), vec!["a\nb", "c", "d"]);
+        assert_eq!(blocks("\n\n").count(), 0);
+
+        let err = ParseError::at("c", "c", "bad").within(input, blocks(input).nth(1).unwrap());
+        assert_eq!((err.line, err.column), (6, 1));
+    }
+
+    #[test]
+    fn helpers_fail_instead_of_panicking() {
+        assert_eq!(split_once("a: b", ": "), Ok(("a", "b")));
+        assert_eq!(
+            split_once("a b", ": "),
+            Err(ParseError::new(1, 1, "expecting `: `"))
+        );
+        assert_eq!(number::<u32>("42"), Ok(42));
+        assert!(number::<u32>("-42").is_err());
+    }
+
+    #[test]
+    fn parses_labelled_lines() {
+        let mut game = Parser::new("Game 12: 3 blue");
+        game.tag("Game").unwrap();
+        assert_eq!(game.number::<u32>(), Ok(12));
+        game.tag(":").unwrap();
+        assert_eq!(game.number::<i32>(), Ok(3));
+        assert_eq!(game.word(), Ok("blue"));
+        assert_eq!(game.end(), Ok(()));
+    }
+
+    #[test]
+    fn errors_point_into_the_line() {
+        let mut card = Parser::new("Card 1: 41 x8 | 83");
+        card.tag("Card").unwrap().number::<u32>().unwrap();
+        card.tag(":").unwrap();
+        let err = card.until("|").unwrap().numbers::<u32>().unwrap_err();
+        assert_eq!((err.line, err.column), (1, 12));
+
+        let err = Parser::new("Card 1").tag("Game").unwrap_err();
+        assert_eq!(err, ParseError::new(1, 1, "expecting `Game`"));
+    }
+
+    #[test]
+    fn derives_line_parsers() {
+        let cards: Vec<Card> = lines("Card 1: 41 48 | 83 86  6\nCard  2: 13 | 61").unwrap();
+        assert_eq!(
+            cards[1],
+            Card {
+                number: 2,
+                winning: vec![13],
+                numbers: vec![61]
+            }
+        );
+
+        let named = Named::try_from("pos @ 1, -2, 3!").unwrap();
+        assert_eq!(named.name, "pos");
+        assert_eq!(named.position, vec![1, -2, 3]);
+    }
+
+    #[test]
+    fn derived_errors_point_to_fields() {
+        let err = lines::<Card>("Card 1: 41 | 83\nCard 2: 41 x | 83").unwrap_err();
+        assert_eq!(err, ParseError::new(2, 12, "`winning`: cannot parse `x`"));
+
+        let err = Card::try_from("Card x: 41 | 83").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 6));
+
+        let err = Card::try_from("Card 1: 41 83").unwrap_err();
+        assert_eq!(err.message, "expecting ` | ` after `winning`");
+
+        let err = Named::try_from("pos @ 1, 2! extra").unwrap_err();
+        assert_eq!(err.message, "unexpected trailing input");
+    }
+
+    #[test]
+    fn derives_parsers_despite_shadowed_names() {
+        let values = shadowed::Values::try_from("a = 1, 2").unwrap();
+        assert_eq!((values.name.as_str(), values.values), ("a", vec![1, 2]));
+        let err = shadowed::Values::try_from("a = 1, x").unwrap_err();
+        assert_eq!(err.message, "`values`: cannot parse `x`");
+    }
+}
@@ -0,0 +1,309 @@
+//! Positions and directions on a two-dimensional plane, with `y` growing downwards.
+
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A point on the plane, which can be outside of a [`Grid`](crate::grid::Grid).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    /// Returns the Manhattan distance between two points.
+    pub fn manhattan(self, other: Point) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Returns `self + delta` if it lies within `0..width` and `0..height`.
+    pub fn add_within(self, delta: Point, width: usize, height: usize) -> Option<Point> {
+        let p = self + delta;
+        let in_bounds = usize::try_from(p.x).is_ok_and(|x| x < width)
+            && usize::try_from(p.y).is_ok_and(|y| y < height);
+        in_bounds.then_some(p)
+    }
+
+    /// Returns the point one step away in direction `dir`.
+    #[must_use]
+    pub fn step(self, dir: impl Into<Dir8>) -> Point {
+        self + dir.into().delta()
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Self {
+        Self::new(x as isize, y as isize)
+    }
+}
+
+impl From<(isize, isize)> for Point {
+    fn from((x, y): (isize, isize)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Self::Output {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Point) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Self::Output {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Point) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<isize> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Self::Output {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// One of the four orthogonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    /// Every direction, clockwise from [`Dir4::Up`].
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    /// Returns the offset of one step in this direction.
+    pub const fn delta(self) -> Point {
+        match self {
+            Dir4::Up => Point::new(0, -1),
+            Dir4::Right => Point::new(1, 0),
+            Dir4::Down => Point::new(0, 1),
+            Dir4::Left => Point::new(-1, 0),
+        }
+    }
+
+    /// Returns the direction after a quarter turn counterclockwise.
+    #[must_use]
+    pub const fn turn_left(self) -> Self {
+        match self {
+            Dir4::Up => Dir4::Left,
+            Dir4::Right => Dir4::Up,
+            Dir4::Down => Dir4::Right,
+            Dir4::Left => Dir4::Down,
+        }
+    }
+
+    /// Returns the direction after a quarter turn clockwise.
+    #[must_use]
+    pub const fn turn_right(self) -> Self {
+        match self {
+            Dir4::Up => Dir4::Right,
+            Dir4::Right => Dir4::Down,
+            Dir4::Down => Dir4::Left,
+            Dir4::Left => Dir4::Up,
+        }
+    }
+
+    /// Returns the opposite direction.
+    #[must_use]
+    pub const fn reverse(self) -> Self {
+        match self {
+            Dir4::Up => Dir4::Down,
+            Dir4::Right => Dir4::Left,
+            Dir4::Down => Dir4::Up,
+            Dir4::Left => Dir4::Right,
+        }
+    }
+
+    pub const fn is_vertical(self) -> bool {
+        matches!(self, Dir4::Up | Dir4::Down)
+    }
+
+    pub const fn is_horizontal(self) -> bool {
+        !self.is_vertical()
+    }
+}
+
+impl Add<Dir4> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Dir4) -> Self::Output {
+        self + rhs.delta()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// One of the four orthogonal and four diagonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir8 {
+    /// Every direction, clockwise from [`Dir8::Up`].
+    pub const ALL: [Dir8; 8] = [
+        Dir8::Up,
+        Dir8::UpRight,
+        Dir8::Right,
+        Dir8::DownRight,
+        Dir8::Down,
+        Dir8::DownLeft,
+        Dir8::Left,
+        Dir8::UpLeft,
+    ];
+
+    /// Returns the offset of one step in this direction.
+    pub const fn delta(self) -> Point {
+        match self {
+            Dir8::Up => Point::new(0, -1),
+            Dir8::UpRight => Point::new(1, -1),
+            Dir8::Right => Point::new(1, 0),
+            Dir8::DownRight => Point::new(1, 1),
+            Dir8::Down => Point::new(0, 1),
+            Dir8::DownLeft => Point::new(-1, 1),
+            Dir8::Left => Point::new(-1, 0),
+            Dir8::UpLeft => Point::new(-1, -1),
+        }
+    }
+
+    /// Returns the direction after an eighth of a turn counterclockwise.
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    /// Returns the direction after an eighth of a turn clockwise.
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Returns the opposite direction.
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    pub const fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        match dir {
+            Dir4::Up => Dir8::Up,
+            Dir4::Right => Dir8::Right,
+            Dir4::Down => Dir8::Down,
+            Dir4::Left => Dir8::Left,
+        }
+    }
+}
+
+impl Add<Dir8> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Dir8) -> Self::Output {
+        self + rhs.delta()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Dir4, Dir8, Point};
+
+    #[test]
+    fn turns() {
+        for dir in Dir4::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.reverse());
+            assert_eq!(dir.delta() + dir.reverse().delta(), Point::ORIGIN);
+        }
+        for dir in Dir8::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.delta() + dir.reverse().delta(), Point::ORIGIN);
+        }
+        assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+        assert_eq!(Dir8::Up.turn_left(), Dir8::UpLeft);
+    }
+
+    #[test]
+    fn dir4_matches_dir8() {
+        for dir in Dir4::ALL {
+            assert_eq!(dir.delta(), Dir8::from(dir).delta());
+            assert!(!Dir8::from(dir).is_diagonal());
+        }
+    }
+
+    #[test]
+    fn arithmetic() {
+        let p = Point::new(1, 2);
+        assert_eq!(p + Dir4::Left, Point::new(0, 2));
+        assert_eq!(p.step(Dir8::UpLeft), Point::new(0, 1));
+        assert_eq!(p * 3 - p, Point::new(2, 4));
+        assert_eq!(p.manhattan(Point::new(-2, 5)), 6);
+    }
+
+    #[test]
+    fn add_within_bounds() {
+        let p = Point::new(0, 1);
+        assert_eq!(p.add_within(Dir4::Left.delta(), 3, 3), None);
+        assert_eq!(p.add_within(Dir4::Down.delta(), 3, 2), None);
+        assert_eq!(
+            p.add_within(Dir4::Right.delta(), 3, 2),
+            Some(Point::new(1, 1))
+        );
+    }
+}
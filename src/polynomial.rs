@@ -0,0 +1,199 @@
+//! Extrapolation of sequences that follow a polynomial, with Newton's forward differences.
+
+use std::fmt::Display;
+
+/// The polynomial `f` with `f(i) = values[i]` for the values it was fitted on.
+///
+/// Every coefficient is an integer difference, so the polynomial is evaluated exactly at any
+/// integer position, before or after the fitted values.
+///
+/// ```
+/// # use advent_of_code::polynomial::Polynomial;
+/// let f = Polynomial::fit(&[1, 3, 6, 10, 15, 21]).unwrap();
+/// assert_eq!(f.degree(), 2);
+/// assert_eq!(f.eval(6), Some(28));
+/// assert_eq!(f.eval(-1), Some(0));
+/// assert_eq!(f.eval(26501365), Some(351_161_213_183_661));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    /// The differences `Δ^j f(0)`, for `j` from 0 to the degree.
+    differences: Vec<i128>,
+}
+
+/// The error returned when a sequence is too short to tell whether it follows a polynomial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotPolynomial {
+    /// The number of values of the sequence.
+    pub len: usize,
+}
+
+impl Display for NotPolynomial {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the {} values do not follow a polynomial of degree below {}",
+            self.len,
+            self.len.saturating_sub(1)
+        )
+    }
+}
+
+impl std::error::Error for NotPolynomial {}
+
+impl Polynomial {
+    /// Fits a polynomial on `values`, taken at positions `0, 1, 2, ...`.
+    ///
+    /// Any `n` values fit a polynomial of degree `n - 1`, so this requires at least one more
+    /// value than the degree needs: the differences must reach a row of zeros. The exception is
+    /// a single value, which is taken as a constant.
+    pub fn fit(values: &[i128]) -> Result<Self, ExtrapolateError> {
+        // the difference table is computed in place: after pass `j`, `table[j..]` holds the
+        // differences of order `j`, and `table[j]` is `Δ^j f(0)`.
+        let mut table = values.to_vec();
+
+        for j in 0..table.len() {
+            if j > 0 {
+                for i in (j..table.len()).rev() {
+                    table[i] = table[i]
+                        .checked_sub(table[i - 1])
+                        .ok_or(ExtrapolateError::Overflow)?;
+                }
+            }
+            if table[j..].iter().all(|d| *d == 0) {
+                table.truncate(j);
+                return Ok(Self { differences: table });
+            }
+        }
+
+        if let [value] = values {
+            return Ok(Self {
+                differences: vec![*value],
+            });
+        }
+
+        Err(NotPolynomial { len: values.len() }.into())
+    }
+
+    /// Returns the degree of the polynomial, with 0 for a constant or zero sequence.
+    pub fn degree(&self) -> usize {
+        self.differences.len().saturating_sub(1)
+    }
+
+    /// Returns the value of the polynomial at `x`, or [`None`] if any of its terms overflows.
+    /// Negative positions extrapolate backwards.
+    pub fn eval(&self, x: i128) -> Option<i128> {
+        // f(x) = sum of Δ^j f(0) * C(x, j), where C(x, j) is an integer for any integer x.
+        let mut binomial: i128 = 1;
+        let mut sum: i128 = 0;
+
+        for (j, d) in self.differences.iter().enumerate() {
+            if j > 0 {
+                // C(x, j) = C(x, j - 1) * (x - j + 1) / j, and the division is exact.
+                binomial = binomial.checked_mul(x.checked_sub(j as i128 - 1)?)? / j as i128;
+            }
+            sum = sum.checked_add(d.checked_mul(binomial)?)?;
+        }
+
+        Some(sum)
+    }
+}
+
+/// An error which can be returned when extrapolating a sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtrapolateError {
+    NotPolynomial(NotPolynomial),
+    /// The extrapolated value does not fit in an `i128`.
+    Overflow,
+}
+
+impl From<NotPolynomial> for ExtrapolateError {
+    fn from(error: NotPolynomial) -> Self {
+        Self::NotPolynomial(error)
+    }
+}
+
+impl Display for ExtrapolateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExtrapolateError::NotPolynomial(error) => write!(f, "{error}"),
+            ExtrapolateError::Overflow => write!(f, "the extrapolated value overflows"),
+        }
+    }
+}
+
+impl std::error::Error for ExtrapolateError {}
+
+/// Returns the value `steps` positions after the last of `values`, or before the first one
+/// if `steps` is negative.
+pub fn extrapolate(values: &[i128], steps: i128) -> Result<i128, ExtrapolateError> {
+    let f = Polynomial::fit(values)?;
+    let x = if steps >= 0 {
+        values.len() as i128 - 1 + steps
+    } else {
+        steps
+    };
+    f.eval(x).ok_or(ExtrapolateError::Overflow)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extrapolate, ExtrapolateError, NotPolynomial, Polynomial};
+
+    #[test]
+    fn fits_cubic() {
+        let f = |x: i128| 2 * x * x * x - 7 * x + 3;
+        let values: Vec<i128> = (0..6).map(f).collect();
+        let fitted = Polynomial::fit(&values).unwrap();
+
+        assert_eq!(fitted.degree(), 3);
+        for x in -20..20 {
+            assert_eq!(fitted.eval(x), Some(f(x)));
+        }
+    }
+
+    #[test]
+    fn extrapolates_both_ways() {
+        let values = [10, 13, 16, 21, 30, 45];
+        assert_eq!(extrapolate(&values, 1), Ok(68));
+        assert_eq!(extrapolate(&values, -1), Ok(5));
+        assert_eq!(extrapolate(&[0, 0, 0], 3), Ok(0));
+    }
+
+    #[test]
+    fn rejects_non_polynomial() {
+        let powers: Vec<i128> = (0..8).map(|i| 1 << i).collect();
+        assert_eq!(
+            Polynomial::fit(&powers),
+            Err(ExtrapolateError::NotPolynomial(NotPolynomial { len: 8 }))
+        );
+        assert_eq!(
+            Polynomial::fit(&[]),
+            Err(ExtrapolateError::NotPolynomial(NotPolynomial { len: 0 }))
+        );
+    }
+
+    #[test]
+    fn extrapolates_single_value_as_constant() {
+        assert_eq!(Polynomial::fit(&[7]).unwrap().degree(), 0);
+        assert_eq!(extrapolate(&[7], 1), Ok(7));
+        assert_eq!(extrapolate(&[7], -3), Ok(7));
+    }
+
+    #[test]
+    fn reports_overflow() {
+        let f = Polynomial::fit(&[0, 1, 4, 9]).unwrap();
+        assert_eq!(f.eval(i128::MAX / 2), None);
+        assert_eq!(f.eval(i128::MIN), None);
+        assert_eq!(
+            extrapolate(&[0, 1, 4, 9], i128::MAX / 2),
+            Err(ExtrapolateError::Overflow)
+        );
+        assert_eq!(
+            Polynomial::fit(&[i128::MIN, i128::MAX]),
+            Err(ExtrapolateError::Overflow)
+        );
+    }
+}
@@ -0,0 +1,290 @@
+//! Sets of integers stored as sorted, disjoint ranges.
+
+use std::fmt::Display;
+use std::ops::Range;
+
+/// A set of integers, stored as sorted ranges that neither overlap nor touch.
+///
+/// ```
+/// # use advent_of_code::ranges::RangeSet;
+/// let seeds = RangeSet::from_iter([79..93, 55..68]);
+/// // seeds from 50 to 97 become soil 52 to 99.
+/// let soil = seeds.map(&[(98..100, -48), (50..98, 2)]);
+/// assert_eq!(soil, RangeSet::from_iter([57..70, 81..95]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct RangeSet {
+    ranges: Vec<Range<i64>>,
+}
+
+impl RangeSet {
+    /// Creates an empty [`RangeSet`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the ranges of the set, in ascending order.
+    pub fn ranges(&self) -> &[Range<i64>] {
+        &self.ranges
+    }
+
+    /// Returns the number of integers in the set.
+    pub fn len(&self) -> u64 {
+        self.ranges.iter().map(|r| r.start.abs_diff(r.end)).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns the smallest integer of the set.
+    pub fn min(&self) -> Option<i64> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    /// Returns the largest integer of the set.
+    pub fn max(&self) -> Option<i64> {
+        self.ranges.last().map(|r| r.end - 1)
+    }
+
+    pub fn contains(&self, n: i64) -> bool {
+        // the ranges are sorted, find the last one that starts at or before `n`.
+        let i = self.ranges.partition_point(|r| r.start <= n);
+        i > 0 && self.ranges[i - 1].end > n
+    }
+
+    /// Adds the integers of `range` to the set.
+    pub fn insert(&mut self, range: Range<i64>) {
+        self.ranges.push(range);
+        self.normalize();
+    }
+
+    /// Returns the integers that are in either set.
+    #[must_use]
+    pub fn union(&self, other: &RangeSet) -> RangeSet {
+        self.ranges
+            .iter()
+            .chain(other.ranges.iter())
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the integers that are in both sets.
+    #[must_use]
+    pub fn intersection(&self, other: &RangeSet) -> RangeSet {
+        let mut ranges = vec![];
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+            if start < end {
+                ranges.push(start..end);
+            }
+            // drop the range that ends first, it cannot overlap anything else.
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { ranges }
+    }
+
+    /// Returns the integers of `self` that are not in `other`.
+    #[must_use]
+    pub fn difference(&self, other: &RangeSet) -> RangeSet {
+        let mut ranges = vec![];
+        let mut j = 0;
+
+        for range in &self.ranges {
+            let mut start = range.start;
+            // skip the ranges of `other` that end before this one.
+            while j < other.ranges.len() && other.ranges[j].end <= start {
+                j += 1;
+            }
+            let mut k = j;
+            while k < other.ranges.len() && other.ranges[k].start < range.end {
+                if other.ranges[k].start > start {
+                    ranges.push(start..other.ranges[k].start);
+                }
+                start = start.max(other.ranges[k].end);
+                k += 1;
+            }
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+
+        Self { ranges }
+    }
+
+    /// Returns the set with `offset` added to every integer.
+    #[must_use]
+    pub fn shift(&self, offset: i64) -> RangeSet {
+        Self {
+            ranges: self
+                .ranges
+                .iter()
+                .map(|r| r.start + offset..r.end + offset)
+                .collect(),
+        }
+    }
+
+    /// Maps the set piecewise through `rules`, each made of a source range and an offset.
+    /// Integers in a source range are shifted by its offset, the first matching rule wins.
+    /// Integers that match no rule are kept as they are.
+    #[must_use]
+    pub fn map(&self, rules: &[(Range<i64>, i64)]) -> RangeSet {
+        let mut remaining = self.clone();
+        let mut mapped = RangeSet::new();
+
+        for (source, offset) in rules {
+            let source = RangeSet::from(source.clone());
+            mapped = mapped.union(&remaining.intersection(&source).shift(*offset));
+            remaining = remaining.difference(&source);
+        }
+
+        mapped.union(&remaining)
+    }
+
+    /// Sorts the ranges and merges the ones that overlap or touch.
+    fn normalize(&mut self) {
+        self.ranges.retain(|r| r.start < r.end);
+        self.ranges.sort_unstable_by_key(|r| r.start);
+
+        let mut merged: Vec<Range<i64>> = Vec::with_capacity(self.ranges.len());
+        for range in self.ranges.drain(..) {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        self.ranges = merged;
+    }
+}
+
+impl From<Range<i64>> for RangeSet {
+    fn from(range: Range<i64>) -> Self {
+        Self::from_iter([range])
+    }
+}
+
+impl FromIterator<Range<i64>> for RangeSet {
+    fn from_iter<T: IntoIterator<Item = Range<i64>>>(iter: T) -> Self {
+        let mut set = Self {
+            ranges: iter.into_iter().collect(),
+        };
+        set.normalize();
+        set
+    }
+}
+
+impl Display for RangeSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ranges: Vec<String> = self.ranges.iter().map(|r| format!("{r:?}")).collect();
+        write!(f, "{{{}}}", ranges.join(", "))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::RangeSet;
+    use std::collections::BTreeSet;
+    use std::ops::Range;
+
+    const DOMAIN: Range<i64> = -16..96;
+
+    /// A small linear congruential generator, so that the checks are reproducible.
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self, range: Range<i64>) -> i64 {
+            self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1);
+            range.start + ((self.0 >> 33) % range.start.abs_diff(range.end)) as i64
+        }
+
+        fn range(&mut self) -> Range<i64> {
+            let start = self.next(0..64);
+            start..start + self.next(0..12)
+        }
+
+        fn set(&mut self) -> (RangeSet, BTreeSet<i64>) {
+            let ranges: Vec<Range<i64>> = (0..self.next(0..5)).map(|_| self.range()).collect();
+            let naive = ranges.iter().flat_map(|r| r.clone()).collect();
+            (ranges.into_iter().collect(), naive)
+        }
+    }
+
+    fn to_naive(set: &RangeSet) -> BTreeSet<i64> {
+        DOMAIN.filter(|n| set.contains(*n)).collect()
+    }
+
+    fn assert_canonical(set: &RangeSet) {
+        for pair in set.ranges().windows(2) {
+            assert!(pair[0].end < pair[1].start, "{set} is not canonical");
+        }
+        assert!(set.ranges().iter().all(|r| r.start < r.end));
+    }
+
+    #[test]
+    fn merges_ranges() {
+        let set = RangeSet::from_iter([5..8, 0..2, 2..3, 7..10, 4..4]);
+        assert_eq!(set.ranges(), &[0..3, 5..10]);
+        assert_eq!(set.len(), 8);
+        assert_eq!((set.min(), set.max()), (Some(0), Some(9)));
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = Lcg(2023);
+
+        for _ in 0..500 {
+            let (a, naive_a) = rng.set();
+            let (b, naive_b) = rng.set();
+
+            let union = a.union(&b);
+            let intersection = a.intersection(&b);
+            let difference = a.difference(&b);
+            for set in [&union, &intersection, &difference] {
+                assert_canonical(set);
+            }
+
+            assert_eq!(to_naive(&a), naive_a);
+            assert_eq!(to_naive(&union), &naive_a | &naive_b);
+            assert_eq!(to_naive(&intersection), &naive_a & &naive_b);
+            assert_eq!(to_naive(&difference), &naive_a - &naive_b);
+            assert_eq!(a.len(), naive_a.len() as u64);
+        }
+    }
+
+    #[test]
+    fn map_matches_brute_force() {
+        let mut rng = Lcg(25);
+
+        for _ in 0..500 {
+            let (set, naive) = rng.set();
+            let rules: Vec<(Range<i64>, i64)> = (0..rng.next(0..4))
+                .map(|_| (rng.range(), rng.next(-8..8)))
+                .collect();
+
+            let mapped = set.map(&rules);
+            assert_canonical(&mapped);
+
+            let naive_mapped: BTreeSet<i64> = naive
+                .iter()
+                .map(|n| {
+                    rules
+                        .iter()
+                        .find(|(source, _)| source.contains(n))
+                        .map_or(*n, |(_, offset)| n + offset)
+                })
+                .collect();
+            assert_eq!(to_naive(&mapped), naive_mapped);
+        }
+    }
+}
@@ -0,0 +1,94 @@
+//! Empirical complexity of solutions, measured on generated inputs of growing sizes.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::fuzz::Generator;
+
+/// The size of the generated inputs that the factors given to `--scale` multiply.
+pub const BASE_SIZE: usize = 10;
+
+/// How long each size is benched for, at most (but at least once).
+const BENCH_TIME: Duration = Duration::from_millis(200);
+
+/// Returns the average time `func` takes on the input of each size made by `generate`.
+pub fn measure<R>(func: &dyn Fn(&str) -> R, generate: Generator, sizes: &[usize]) -> Vec<Duration> {
+    sizes
+        .iter()
+        .map(|size| {
+            let input = generate(*size, 0);
+            let timer = Instant::now();
+            let mut runs = 0;
+            while runs == 0 || (timer.elapsed() < BENCH_TIME && runs < 1000) {
+                black_box(func(black_box(&input)));
+                runs += 1;
+            }
+            timer.elapsed() / runs
+        })
+        .collect()
+}
+
+/// Fits `time = c * size ^ k` to the samples by least squares on their logarithms, and returns
+/// the exponent `k`. Returns [`None`] with less than two distinct sizes.
+///
+/// ```
+/// # use advent_of_code::scale::fit_exponent;
+/// # use std::time::Duration;
+/// let times = [10, 20, 40].map(|n: u64| Duration::from_nanos(3 * n * n));
+/// let k = fit_exponent(&[10, 20, 40], &times).unwrap();
+/// assert!((k - 2.0).abs() < 1e-9);
+/// ```
+pub fn fit_exponent(sizes: &[usize], times: &[Duration]) -> Option<f64> {
+    let points: Vec<(f64, f64)> = sizes
+        .iter()
+        .zip(times)
+        .map(|(size, time)| {
+            let nanos = time.as_nanos().max(1) as f64;
+            ((*size as f64).ln(), nanos.ln())
+        })
+        .collect();
+
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let covariance: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+
+    (variance > 0.0).then(|| covariance / variance)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fit_exponent, measure};
+    use std::time::Duration;
+
+    #[test]
+    fn fits_noisy_power_laws() {
+        let sizes = [10, 20, 40, 80];
+        let times = [1_000, 2_100, 3_900, 8_200].map(Duration::from_nanos);
+        let k = fit_exponent(&sizes, &times).unwrap();
+        assert!((k - 1.0).abs() < 0.05, "{k}");
+
+        let times = [1_000, 8_000, 64_000, 512_000].map(Duration::from_nanos);
+        assert!((fit_exponent(&sizes, &times).unwrap() - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn needs_two_sizes() {
+        let time = Duration::from_micros(1);
+        assert_eq!(fit_exponent(&[10], &[time]), None);
+        assert_eq!(fit_exponent(&[10, 10], &[time, time]), None);
+    }
+
+    #[test]
+    fn measures_each_size() {
+        let generate = |size: usize, _| "x".repeat(size);
+        let times = measure(&|input: &str| input.len(), generate, &[1, 2, 3]);
+        assert_eq!(times.len(), 3);
+    }
+}
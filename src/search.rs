@@ -0,0 +1,338 @@
+//! Path finding over any state type, given a function that yields the successors of a state.
+
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// A path found by a search, from the start state to a goal state (both included).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S> {
+    /// The sum of the weights of the path, or its number of steps for unweighted searches.
+    pub cost: usize,
+    pub states: Vec<S>,
+}
+
+impl<S> Path<S> {
+    /// Returns the goal state the path leads to.
+    pub fn goal(&self) -> &S {
+        // NOTE: a path always contains at least the start state.
+        self.states.last().unwrap()
+    }
+}
+
+/// Finds the cheapest path from `start` to a state matching `is_goal` with Dijkstra's algorithm.
+/// `successors` yields the states reachable from a state, with the weight of each move.
+///
+/// ```
+/// # use advent_of_code::search::dijkstra;
+/// // reach 9 from 0, with +1 costing 1 and +3 costing 2.
+/// let path = dijkstra(0, |n| [(n + 1, 1), (n + 3, 2)], |n| *n == 9).unwrap();
+/// assert_eq!(path.cost, 6);
+/// assert_eq!(path.states, vec![0, 3, 6, 9]);
+/// ```
+pub fn dijkstra<S, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    best_first(BinaryHeap::new(), start, successors, |_| 0, is_goal)
+}
+
+/// Same as [`dijkstra`], backed by a bucket queue (Dial's algorithm).
+/// This is faster when weights are small integers, e.g. the digits of a grid.
+pub fn dial<S, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    best_first(BucketQueue::new(), start, successors, |_| 0, is_goal)
+}
+
+/// Finds the cheapest path from `start` to a state matching `is_goal` with the A* algorithm.
+/// `heuristic` must never overestimate the cost from a state to the goal, e.g. the Manhattan
+/// distance on a grid where every move costs at least 1.
+pub fn astar<S, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> usize,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    best_first(BinaryHeap::new(), start, successors, heuristic, is_goal)
+}
+
+/// Finds the shortest path from `start` to a state matching `is_goal`, where every move costs 1.
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut states = vec![start.clone()];
+    let mut parents = vec![usize::MAX];
+    let mut seen = HashSet::from([start]);
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(i) = queue.pop_front() {
+        if is_goal(&states[i]) {
+            return Some(reconstruct(&states, &parents, i, |path| path.len() - 1));
+        }
+        for next in successors(&states[i]) {
+            if seen.insert(next.clone()) {
+                queue.push_back(states.len());
+                states.push(next);
+                parents.push(i);
+            }
+        }
+    }
+
+    None
+}
+
+/// Finds any path from `start` to a state matching `is_goal`, exploring depth first.
+/// The path is not necessarily the shortest one.
+pub fn dfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut states = vec![start.clone()];
+    let mut parents = vec![usize::MAX];
+    let mut seen = HashSet::from([start]);
+    let mut stack = vec![0];
+
+    while let Some(i) = stack.pop() {
+        if is_goal(&states[i]) {
+            return Some(reconstruct(&states, &parents, i, |path| path.len() - 1));
+        }
+        for next in successors(&states[i]) {
+            if seen.insert(next.clone()) {
+                stack.push(states.len());
+                states.push(next);
+                parents.push(i);
+            }
+        }
+    }
+
+    None
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A priority queue of `(cost, state index)`, popping the lowest priority first.
+trait Frontier {
+    fn push(&mut self, priority: usize, item: (usize, usize));
+    fn pop(&mut self) -> Option<(usize, usize)>;
+}
+
+impl Frontier for BinaryHeap<Reverse<(usize, usize, usize)>> {
+    fn push(&mut self, priority: usize, (cost, i): (usize, usize)) {
+        BinaryHeap::push(self, Reverse((priority, cost, i)));
+    }
+
+    fn pop(&mut self) -> Option<(usize, usize)> {
+        BinaryHeap::pop(self).map(|Reverse((_, cost, i))| (cost, i))
+    }
+}
+
+/// A priority queue with one bucket per priority, starting at the lowest priority in the queue.
+/// Priorities pushed must not be lower than the last one popped.
+struct BucketQueue<T> {
+    base: usize,
+    buckets: VecDeque<Vec<T>>,
+    len: usize,
+}
+
+impl<T> BucketQueue<T> {
+    fn new() -> Self {
+        Self {
+            base: 0,
+            buckets: VecDeque::new(),
+            len: 0,
+        }
+    }
+}
+
+impl Frontier for BucketQueue<(usize, usize)> {
+    fn push(&mut self, priority: usize, item: (usize, usize)) {
+        assert!(
+            priority >= self.base,
+            "priority is lower than the last popped"
+        );
+        let index = priority - self.base;
+        if self.buckets.len() <= index {
+            self.buckets.resize_with(index + 1, Vec::new);
+        }
+        self.buckets[index].push(item);
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<(usize, usize)> {
+        if self.len == 0 {
+            return None;
+        }
+        loop {
+            if let Some(item) = self.buckets.front_mut()?.pop() {
+                self.len -= 1;
+                return Some(item);
+            }
+            self.buckets.pop_front();
+            self.base += 1;
+        }
+    }
+}
+
+fn best_first<S, I>(
+    mut frontier: impl Frontier,
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> usize,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    frontier.push(heuristic(&start), (0, 0));
+    let mut states = vec![start.clone()];
+    let mut index = HashMap::from([(start, 0)]);
+    let mut costs = vec![0];
+    let mut parents = vec![usize::MAX];
+
+    while let Some((cost, i)) = frontier.pop() {
+        // skip entries that were superseded by a cheaper path.
+        if cost > costs[i] {
+            continue;
+        }
+        if is_goal(&states[i]) {
+            return Some(reconstruct(&states, &parents, i, |_| cost));
+        }
+        for (next, weight) in successors(&states[i]) {
+            let next_cost = cost + weight;
+            let j = match index.entry(next) {
+                Entry::Occupied(e) => {
+                    let j = *e.get();
+                    if next_cost >= costs[j] {
+                        continue;
+                    }
+                    costs[j] = next_cost;
+                    parents[j] = i;
+                    j
+                }
+                Entry::Vacant(e) => {
+                    let j = states.len();
+                    states.push(e.key().clone());
+                    e.insert(j);
+                    costs.push(next_cost);
+                    parents.push(i);
+                    j
+                }
+            };
+            frontier.push(next_cost + heuristic(&states[j]), (next_cost, j));
+        }
+    }
+
+    None
+}
+
+fn reconstruct<S: Clone>(
+    states: &[S],
+    parents: &[usize],
+    goal: usize,
+    cost: impl FnOnce(&[S]) -> usize,
+) -> Path<S> {
+    let mut path = vec![states[goal].clone()];
+    let mut i = goal;
+    while parents[i] != usize::MAX {
+        i = parents[i];
+        path.push(states[i].clone());
+    }
+    path.reverse();
+
+    Path {
+        cost: cost(&path),
+        states: path,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{astar, bfs, dfs, dial, dijkstra};
+    use crate::grid::Grid;
+
+    fn get_maze() -> Grid<bool> {
+        Grid::parse("..#.\n.##.\n....\n#.#.", |c| c == '#').unwrap()
+    }
+
+    #[test]
+    fn weighted_searches_agree() {
+        let successors = |n: &u32| [(n + 1, 5), (n * 2, 3), (n.saturating_sub(1), 1)];
+        let a = dijkstra(1, successors, |n| *n == 37).unwrap();
+        let b = dial(1, successors, |n| *n == 37).unwrap();
+        let c = astar(1, successors, |_| 0, |n| *n == 37).unwrap();
+        assert_eq!(a.cost, b.cost);
+        assert_eq!(a.cost, c.cost);
+        assert_eq!(a.states.first(), Some(&1));
+        assert_eq!(b.goal(), &37);
+    }
+
+    #[test]
+    fn astar_on_grid() {
+        let maze = get_maze();
+        let end = (3, 0);
+        let path = astar(
+            (0, 0),
+            |p| {
+                maze.neighbours4(*p)
+                    .filter(|n| !maze[*n])
+                    .map(|n| (n, 1))
+                    .collect::<Vec<_>>()
+            },
+            |p: &(usize, usize)| p.0.abs_diff(end.0) + p.1.abs_diff(end.1),
+            |p| *p == end,
+        )
+        .unwrap();
+        assert_eq!(path.cost, 7);
+        assert_eq!(path.states.len(), 8);
+    }
+
+    #[test]
+    fn unweighted_searches() {
+        let maze = get_maze();
+        let successors = |p: &(usize, usize)| {
+            maze.neighbours4(*p)
+                .filter(|n| !maze[*n])
+                .collect::<Vec<_>>()
+        };
+        let path = bfs((0, 0), successors, |p| *p == (3, 3)).unwrap();
+        assert_eq!(path.cost, 6);
+        assert_eq!(path.states.len(), 7);
+
+        let path = dfs((0, 0), successors, |p| *p == (3, 3)).unwrap();
+        assert_eq!(path.goal(), &(3, 3));
+        assert!(path.cost >= 6);
+
+        assert_eq!(bfs((0, 0), successors, |p| *p == (0, 3)), None);
+    }
+}
@@ -0,0 +1,166 @@
+//! Benchmarks of every solved day on its puzzle input, run by `cargo bench`.
+
+use std::env;
+use std::fmt::Display;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::template::runner::{PartResult, Variant};
+use crate::template::{try_read_file, ANSI_BOLD, ANSI_RESET};
+use crate::Day;
+
+/// Receives a variant of a part with its part and name, as a function that returns `true` if
+/// the part gave an answer.
+pub type Callback<'a> = dyn FnMut(u8, &'static str, &dyn Fn(&str) -> bool) + 'a;
+
+/// Passes each variant of each part of a day to a [`Callback`]. Generated by `solution!`.
+pub type Parts = fn(&mut Callback);
+
+/// Passes the variants of a part to `bench`, see [`Parts`].
+pub fn register<R: PartResult>(variants: &[Variant<R>], part: u8, bench: &mut Callback) {
+    for (name, func) in variants {
+        bench(part, name, &|input| func(input).into_result().is_ok());
+    }
+}
+
+/// The number of timed samples of a benchmark.
+const SAMPLES: usize = 50;
+
+/// The least time measured by a sample, so that fast parts are timed over several runs.
+const SAMPLE_TIME: Duration = Duration::from_millis(1);
+
+/// Timing statistics of the samples of a benchmark, each one the average time of a run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub mean: Duration,
+    pub std_dev: Duration,
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub samples: usize,
+    /// The number of samples further than 1.5 times the interquartile range from the quartiles.
+    pub outliers: usize,
+}
+
+impl Stats {
+    /// Computes the statistics of `samples`, or [`None`] if there are none.
+    pub fn new(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<f64> = samples.iter().map(|s| s.as_nanos() as f64).collect();
+        sorted.sort_by(f64::total_cmp);
+
+        let n = sorted.len() as f64;
+        let mean = sorted.iter().sum::<f64>() / n;
+        let variance = sorted.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1.0).max(1.0);
+        let quantile = |q: f64| sorted[((sorted.len() - 1) as f64 * q).round() as usize];
+        let (q1, q3) = (quantile(0.25), quantile(0.75));
+        let fence = 1.5 * (q3 - q1);
+        let nanos = |n: f64| Duration::from_nanos(n.round() as u64);
+
+        Some(Self {
+            mean: nanos(mean),
+            std_dev: nanos(variance.sqrt()),
+            median: nanos(quantile(0.5)),
+            min: nanos(sorted[0]),
+            max: nanos(sorted[sorted.len() - 1]),
+            samples: sorted.len(),
+            outliers: sorted
+                .iter()
+                .filter(|s| **s < q1 - fence || **s > q3 + fence)
+                .count(),
+        })
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{ANSI_BOLD}{:.1?}{ANSI_RESET} ± {:.1?} (median {:.1?}, min {:.1?}, max {:.1?}, {} samples, {} outliers)",
+            self.mean, self.std_dev, self.median, self.min, self.max, self.samples, self.outliers
+        )
+    }
+}
+
+/// Benches the parts of `days` that are solved, i.e. that have an input and give an answer.
+///
+/// Arguments that are not flags filter the benchmarks by name, e.g. `cargo bench -- 05` or
+/// `cargo bench -- 11/part_2`. Benchmarks are named `<day>/part_<n>`, followed by the name of
+/// the variant if the part has several.
+pub fn main(days: &[(u8, Parts)]) {
+    // cargo passes `--bench` to the target.
+    let filters: Vec<String> = env::args()
+        .skip(1)
+        .filter(|a| !a.starts_with("--"))
+        .collect();
+
+    for (day, parts) in days {
+        let Some(day) = Day::new(*day) else { continue };
+        let Ok(input) = try_read_file("inputs", day) else {
+            continue;
+        };
+
+        parts(&mut |part, variant, func| {
+            let name = if variant.starts_with("part_") {
+                format!("{day}/part_{part}")
+            } else {
+                format!("{day}/part_{part}/{variant}")
+            };
+            if !filters.is_empty() && !filters.iter().any(|f| name.contains(f.as_str())) {
+                return;
+            }
+            if !func(&input) {
+                println!("{name:<24} not solved");
+                return;
+            }
+            match Stats::new(&sample(func, &input)) {
+                Some(stats) => println!("{name:<24} {stats}"),
+                None => println!("{name:<24} no samples"),
+            }
+        });
+    }
+}
+
+/// Times [`SAMPLES`] samples of `func`, each over enough runs to last [`SAMPLE_TIME`].
+fn sample(func: &dyn Fn(&str) -> bool, input: &str) -> Vec<Duration> {
+    // the first run warms up the caches, and estimates how many runs a sample needs.
+    let timer = Instant::now();
+    black_box(func(black_box(input)));
+    let estimate = timer.elapsed().max(Duration::from_nanos(1));
+    let runs = (SAMPLE_TIME.as_nanos() / estimate.as_nanos()).clamp(1, 100_000) as u32;
+
+    (0..SAMPLES)
+        .map(|_| {
+            let timer = Instant::now();
+            for _ in 0..runs {
+                black_box(func(black_box(input)));
+            }
+            timer.elapsed() / runs
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Stats;
+    use std::time::Duration;
+
+    #[test]
+    fn computes_stats() {
+        let samples: Vec<Duration> = [10, 12, 11, 13, 9, 10, 11, 44]
+            .map(Duration::from_micros)
+            .to_vec();
+        let stats = Stats::new(&samples).unwrap();
+        assert_eq!(stats.mean, Duration::from_micros(15));
+        assert_eq!(stats.median, Duration::from_micros(11));
+        assert_eq!(stats.min, Duration::from_micros(9));
+        assert_eq!(stats.max, Duration::from_micros(44));
+        assert_eq!(stats.samples, 8);
+        assert_eq!(stats.outliers, 1);
+        assert!(Stats::new(&[]).is_none());
+    }
+}
@@ -4,20 +4,39 @@ use crate::template::{
     readme_benchmarks::{self, Timings},
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
-
-pub fn handle(is_release: bool, is_timed: bool) {
+use crate::{Day, DaySet};
+
+pub fn handle(
+    days: DaySet,
+    only_failed: bool,
+    is_release: bool,
+    is_timed: bool,
+    variant: Option<&str>,
+) {
     let mut timings: Vec<Timings> = vec![];
+    let mut failed = last_run::read_failed();
+
+    let selected = if only_failed {
+        days.intersection(failed)
+    } else {
+        days
+    };
 
-    all_days().for_each(|day| {
-        if day > 1 {
+    if selected.is_empty() {
+        println!("No days to run.");
+        return;
+    }
+
+    selected.iter().enumerate().for_each(|(i, day)| {
+        if i > 0 {
             println!();
         }
 
         println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
         println!("------");
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        let (output, success) =
+            child_commands::run_solution(day, is_timed, is_release, variant).unwrap();
 
         if output.is_empty() {
             println!("Not solved.");
@@ -25,14 +44,26 @@ pub fn handle(is_release: bool, is_timed: bool) {
             let val = child_commands::parse_exec_time(&output, day);
             timings.push(val);
         }
+
+        let answers = child_commands::parse_answers(&output);
+        if success && last_run::check_answers(day, &answers) {
+            failed.remove(day);
+        } else {
+            failed.insert(day);
+        }
     });
 
+    if let Err(e) = last_run::write_failed(failed) {
+        eprintln!("Failed to record the outcome of this run: {e:?}");
+    }
+
     if is_timed {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
         println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
-        if is_release {
+        // a partial run would drop the other days from the benchmark table.
+        if is_release && selected == DaySet::all() {
             match readme_benchmarks::update(timings, total_millis) {
                 Ok(()) => println!("Successfully updated README with benchmarks."),
                 Err(_) => {
@@ -61,10 +92,47 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
+/// Keeps track of the days whose last run failed, so that `all --failed` can re-run only those.
+/// A run fails if the solution exits with an error, a part returns no result, or a result
+/// differs from the answer recorded in `data/answers/<day>.txt` (one line per part).
+mod last_run {
+    use super::Error;
+    use crate::{Day, DaySet};
+    use std::fs;
+
+    static PATH: &str = "data/last_run.txt";
+
+    pub fn read_failed() -> DaySet {
+        fs::read_to_string(PATH)
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .unwrap_or_default()
+    }
+
+    pub fn write_failed(failed: DaySet) -> Result<(), Error> {
+        let days: Vec<String> = failed.iter().map(|d| d.into_inner().to_string()).collect();
+        fs::write(PATH, days.join(","))?;
+        Ok(())
+    }
+
+    pub fn check_answers(day: Day, answers: &[(u8, Option<String>)]) -> bool {
+        let recorded = fs::read_to_string(format!("data/answers/{day}.txt")).unwrap_or_default();
+        let recorded: Vec<&str> = recorded.lines().map(str::trim).collect();
+
+        answers.iter().all(|(part, answer)| match answer {
+            None => false,
+            Some(answer) => recorded
+                .get(*part as usize - 1)
+                .is_none_or(|expected| expected.is_empty() || expected == answer),
+        })
+    }
+}
+
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 mod child_commands {
     use super::{get_path_for_bin, Error};
+    use crate::template::{runner::SINGLE_PART, ANSI_BOLD, ANSI_RESET};
     use crate::Day;
     use std::{
         io::{BufRead, BufReader},
@@ -73,11 +141,17 @@ mod child_commands {
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day.
+    /// Returns the lines written to stdout and whether the bin exited successfully.
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        variant: Option<&str>,
+    ) -> Result<(Vec<String>, bool), Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+            return Ok((vec![], true));
         }
 
         let day_padded = day.to_string();
@@ -87,12 +161,18 @@ mod child_commands {
             args.push("--release");
         }
 
+        args.push("--");
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        if let Some(variant) = variant {
+            args.push("--variant");
+            args.push(variant);
+        }
+
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
@@ -120,9 +200,31 @@ mod child_commands {
         }
 
         thread.join().unwrap();
-        cmd.wait()?;
+        let status = cmd.wait()?;
 
-        Ok(output)
+        Ok((output, status.success()))
+    }
+
+    /// Extracts the result of each part from the output of a solution bin.
+    /// Parts that did not produce a result are [`None`], multi-line results are skipped.
+    pub fn parse_answers(output: &[String]) -> Vec<(u8, Option<String>)> {
+        output
+            .iter()
+            .filter_map(|l| {
+                // intermediate results are overwritten with a carriage return.
+                let line = l.rsplit('\r').next()?;
+                let part = line.strip_prefix("Part ")?;
+                let (part, rest) = part.split_once(':')?;
+                let part = part.parse::<u8>().ok()?;
+
+                if rest.contains('✖') {
+                    return Some((part, None));
+                }
+
+                let answer = rest.split(ANSI_BOLD).nth(1)?.split(ANSI_RESET).next()?;
+                Some((part, Some(answer.to_string())))
+            })
+            .collect()
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
@@ -130,9 +232,12 @@ mod child_commands {
             day,
             part_1: None,
             part_2: None,
+            single_part: false,
             total_nanos: 0_f64,
         };
 
+        timings.single_part = output.iter().any(|l| l.ends_with(SINGLE_PART));
+
         output
             .iter()
             .filter_map(|l| {
@@ -171,7 +276,7 @@ mod child_commands {
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
@@ -202,7 +307,7 @@ mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_answers, parse_exec_time};
 
         use crate::day;
 
@@ -250,5 +355,44 @@ mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn test_single_part() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
+                    "Part 2: - (single-part day)".into(),
+                    "".into(),
+                ],
+                day!(25),
+            );
+            assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.single_part, true);
+            assert_eq!(res.stars(), 2);
+        }
+
+        #[test]
+        fn test_parse_answers() {
+            let res = parse_answers(&[
+                "Part 1: \x1b[1m42\x1b[0m > benching\rPart 1: \x1b[1m42\x1b[0m (1.0ms @ 10 samples)"
+                    .into(),
+                "Part 2: ✖\rPart 2: ✖             ".into(),
+                "".into(),
+            ]);
+            assert_eq!(res, vec![(1, Some("42".into())), (2, None)]);
+        }
+
+        #[test]
+        fn test_variant_rows() {
+            let output = [
+                "Part 1: \x1b[1m374\x1b[0m (119.0µs @ 6032 samples)".into(),
+                "  pairs      119.0µs  1.00x".into(),
+                "  sorted > benching\r  sorted      17.1µs  6.95x  ✖ different result: 375".into(),
+                "".into(),
+            ];
+            let res = parse_exec_time(&output, day!(11));
+            assert_approx_eq!(res.total_nanos, 119000_f64);
+            assert_eq!(parse_answers(&output), vec![(1, Some("374".into()))]);
+        }
     }
 }
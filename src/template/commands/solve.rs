@@ -2,10 +2,40 @@ use std::process::{Command, Stdio};
 
 use crate::Day;
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
+/// The flags of `solve`, passed on to the solution bin.
+#[derive(Debug, Default)]
+pub struct Options {
+    pub release: bool,
+    pub time: bool,
+    pub submit: Option<u8>,
+    pub part: Option<u8>,
+    pub viz: bool,
+    pub verbosity: u8,
+    pub params: Vec<(String, String)>,
+    pub variant: Option<String>,
+    /// `Some` with `--fuzz`, with the number of iterations if it is given.
+    pub fuzz: Option<Option<usize>>,
+    /// The factors of the sizes given with `--scale`.
+    pub scale: Option<Vec<usize>>,
+}
+
+pub fn handle(day: Day, options: &Options) {
+    let Options {
+        release,
+        time,
+        submit: submit_part,
+        part,
+        viz,
+        verbosity,
+        params,
+        variant,
+        fuzz,
+        scale,
+    } = options;
+
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
-    if release {
+    if *release {
         cmd_args.push("--release".to_string());
     }
 
@@ -16,10 +46,44 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
-    if time {
+    if let Some(part) = part {
+        cmd_args.push("--part".to_string());
+        cmd_args.push(part.to_string());
+    }
+
+    if *time {
         cmd_args.push("--time".to_string());
     }
 
+    if *viz {
+        cmd_args.push("--viz".to_string());
+    }
+
+    for _ in 0..*verbosity {
+        cmd_args.push("-v".to_string());
+    }
+
+    if let Some(variant) = variant {
+        cmd_args.push("--variant".to_string());
+        cmd_args.push(variant.to_string());
+    }
+
+    if let Some(iterations) = fuzz {
+        cmd_args.push("--fuzz".to_string());
+        cmd_args.extend(iterations.map(|n| n.to_string()));
+    }
+
+    if let Some(factors) = scale {
+        let factors: Vec<String> = factors.iter().map(ToString::to_string).collect();
+        cmd_args.push("--scale".to_string());
+        cmd_args.push(factors.join(","));
+    }
+
+    for (name, value) in params {
+        cmd_args.push("--param".to_string());
+        cmd_args.push(format!("{name}={value}"));
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -1,7 +1,11 @@
 use crate::Day;
-use std::{env, fs};
+use std::error::Error;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
 
 pub mod aoc_cli;
+pub mod bench;
 pub mod commands;
 pub mod readme_benchmarks;
 pub mod runner;
@@ -9,28 +13,195 @@ pub mod runner;
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_CLEAR_LINE: &str = "\x1b[2K";
 
 /// Helper function that reads a text file to a string.
+///
+/// # Panics
+/// Panics with a hint on how to fix it if the file is missing or empty.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
+    try_read_file(folder, day).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Reads a text file to a string, with normalized line endings and no trailing newline.
+pub fn try_read_file(folder: &str, day: Day) -> Result<String, ReadFileError> {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let path = Path::new("data").join(folder).join(format!("{day}.txt"));
+
+    let content = match fs::read_to_string(cwd.join(&path)) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            return Err(ReadFileError::Missing(path, day));
+        }
+        Err(e) => return Err(ReadFileError::IO(path, e)),
+    };
+
+    let content = normalize(&content);
+    if content.trim().is_empty() {
+        return Err(ReadFileError::Empty(path, day));
+    }
+
+    Ok(content)
+}
+
+/// Converts CRLF line endings to LF and strips trailing newlines.
+#[must_use]
+pub fn normalize(input: &str) -> String {
+    input
+        .replace("\r\n", "\n")
+        .trim_end_matches('\n')
+        .to_string()
+}
+
+/// An error which can be returned when reading a data file.
+#[derive(Debug)]
+pub enum ReadFileError {
+    Missing(PathBuf, Day),
+    Empty(PathBuf, Day),
+    IO(PathBuf, io::Error),
+}
+
+impl ReadFileError {
+    fn hint(path: &Path, day: Day) -> String {
+        if path.starts_with(Path::new("data").join("inputs")) {
+            format!("run `cargo download {}` to fetch it", day.into_inner())
+        } else {
+            "paste the example from the puzzle description into it".into()
+        }
+    }
+}
+
+impl Error for ReadFileError {}
+
+impl Display for ReadFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReadFileError::Missing(path, day) => write!(
+                f,
+                "\"{}\" does not exist, {}.",
+                path.display(),
+                Self::hint(path, *day)
+            ),
+            ReadFileError::Empty(path, day) => write!(
+                f,
+                "\"{}\" is empty, {}.",
+                path.display(),
+                Self::hint(path, *day)
+            ),
+            ReadFileError::IO(path, e) => write!(f, "could not read \"{}\": {e}", path.display()),
+        }
+    }
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+///
+/// Days that only have a first part (e.g. the 25th) can be declared with `solution!(25, part_one)`.
+///
+/// A part can have several implementations, listed after it with a name and the function to run
+/// (the name alone if it is the function). The first one is used unless another is chosen with
+/// `--variant <name>`, and `--time` compares it to the others:
+///
+/// ```ignore
+/// advent_of_code::solution!(11, part_one, part_two => [pairs: part_two, sorted: part_two_sorted]);
+/// ```
+///
+/// A generator of inputs, `fn(size: usize, seed: u64) -> String`, can be given last, for
+/// `--fuzz` to check that the variants agree and `--scale` to measure their complexity:
+///
+/// ```ignore
+/// advent_of_code::solution!(17, part_one, part_two, generate);
+/// ```
 #[macro_export]
 macro_rules! solution {
+    (@variants $part:ident) => {
+        [(stringify!($part), &$part as &dyn Fn(&str) -> _)]
+    };
+    (@variants $part:ident [$($name:ident $(: $func:ident)?),+ $(,)?]) => {
+        [$((stringify!($name), &$crate::solution!(@func $name $($func)?) as &dyn Fn(&str) -> _)),+]
+    };
+    (@func $name:ident) => {
+        $name
+    };
+    (@func $name:ident $func:ident) => {
+        $func
+    };
+    (@generator) => {
+        None
+    };
+    (@generator $generate:ident) => {
+        Some($generate as advent_of_code::fuzz::Generator)
+    };
     ($day:expr) => {
+        $crate::solution!($day, part_one, part_two);
+    };
+    ($day:expr, part_one $(=> [$($one:tt)+])?, part_two $(=> [$($two:tt)+])? $(, $generate:ident)?) => {
+        /// The current day.
+        const DAY: advent_of_code::Day = advent_of_code::day!($day);
+
+        /// Passes each variant of each part to `bench`, for `cargo bench`.
+        #[allow(dead_code)]
+        pub fn bench_parts(bench: &mut advent_of_code::template::bench::Callback) {
+            use advent_of_code::template::bench::register;
+            register(&$crate::solution!(@variants part_one $([$($one)+])?), 1, bench);
+            register(&$crate::solution!(@variants part_two $([$($two)+])?), 2, bench);
+        }
+
+        fn main() {
+            use advent_of_code::template::runner::*;
+            let generate = $crate::solution!(@generator $($generate)?);
+            let input = if uses_puzzle_input() { read_input(DAY) } else { String::new() };
+            let mut agree = true;
+            if is_part_selected(1) {
+                let variants = $crate::solution!(@variants part_one $([$($one)+])?);
+                agree &= run_part_mode(&variants, &input, generate, DAY, 1);
+            }
+            if is_part_selected(2) {
+                let variants = $crate::solution!(@variants part_two $([$($two)+])?);
+                agree &= run_part_mode(&variants, &input, generate, DAY, 2);
+            }
+            if !agree {
+                std::process::exit(1);
+            }
+        }
+    };
+    ($day:expr, part_one $(=> [$($one:tt)+])? $(, $generate:ident)?) => {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
+        /// Passes each variant of the part to `bench`, for `cargo bench`.
+        #[allow(dead_code)]
+        pub fn bench_parts(bench: &mut advent_of_code::template::bench::Callback) {
+            use advent_of_code::template::bench::register;
+            register(&$crate::solution!(@variants part_one $([$($one)+])?), 1, bench);
+        }
+
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            let generate = $crate::solution!(@generator $($generate)?);
+            let input = if uses_puzzle_input() { read_input(DAY) } else { String::new() };
+            let mut agree = true;
+            if is_part_selected(1) {
+                let variants = $crate::solution!(@variants part_one $([$($one)+])?);
+                agree &= run_part_mode(&variants, &input, generate, DAY, 1);
+            }
+            if is_part_selected(2) {
+                print_single_part(2);
+            }
+            if !agree {
+                std::process::exit(1);
+            }
         }
     };
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::normalize;
+
+    #[test]
+    fn normalizes_line_endings() {
+        assert_eq!(normalize("a\r\nb\r\n\r\n"), "a\nb");
+        assert_eq!(normalize("a\n\nb\n"), "a\n\nb");
+    }
+}
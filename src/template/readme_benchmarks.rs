@@ -23,9 +23,19 @@ pub struct Timings {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    /// The day only has a first part, e.g. the 25th.
+    pub single_part: bool,
     pub total_nanos: f64,
 }
 
+impl Timings {
+    /// Returns the number of stars earned by the day.
+    #[must_use]
+    pub fn stars(&self) -> u8 {
+        u8::from(self.part_1.is_some()) + u8::from(self.part_2.is_some() || self.single_part)
+    }
+}
+
 pub struct TablePosition {
     pos_start: usize,
     pos_end: usize,
@@ -69,19 +79,28 @@ fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> St
         "| :---: | :---: | :---:  |".into(),
     ];
 
+    let stars = timings.iter().map(|t| u32::from(t.stars())).sum::<u32>();
+
     for timing in timings {
         let path = get_path_for_bin(timing.day);
+        let part_2 = match (timing.part_2, timing.single_part) {
+            (Some(part_2), _) => format!("`{part_2}`"),
+            (None, true) => "⭐".into(),
+            (None, false) => "`-`".into(),
+        };
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | `{}` | {} |",
             timing.day.into_inner(),
             path,
             timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            part_2
         ));
     }
 
     lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
+    lines.push(format!(
+        "**Total: {total_millis:.2}ms** · **Stars: {stars} ⭐**"
+    ));
     lines.push(MARKER.into());
 
     lines.join("\n")
@@ -113,20 +132,30 @@ mod tests {
                 day: day!(1),
                 part_1: Some("10ms".into()),
                 part_2: Some("20ms".into()),
+                single_part: false,
                 total_nanos: 3e+10,
             },
             Timings {
                 day: day!(2),
                 part_1: Some("30ms".into()),
                 part_2: Some("40ms".into()),
+                single_part: false,
                 total_nanos: 7e+10,
             },
             Timings {
                 day: day!(4),
                 part_1: Some("40ms".into()),
                 part_2: Some("50ms".into()),
+                single_part: false,
                 total_nanos: 9e+10,
             },
+            Timings {
+                day: day!(25),
+                part_1: Some("60ms".into()),
+                part_2: None,
+                single_part: true,
+                total_nanos: 6e+10,
+            },
         ]
     }
 
@@ -175,8 +204,9 @@ mod tests {
             "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
             "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
             "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 25](./src/bin/25.rs) | `60ms` | ⭐ |",
             "",
-            "**Total: 190.00ms**",
+            "**Total: 190.00ms** · **Stars: 8 ⭐**",
             "<!--- benchmarking table --->",
             "baz",
         ]
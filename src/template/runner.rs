@@ -1,6 +1,7 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::fuzz::{self, Generator};
+use crate::template::{aoc_cli, ANSI_CLEAR_LINE, ANSI_ITALIC, ANSI_RESET};
+use crate::{ocr, scale, trace, viz, Day};
 use std::fmt::Display;
 use std::io::{stdout, Write};
 use std::process::Output;
@@ -9,17 +10,330 @@ use std::{cmp, env, process};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Printed in place of the result of a part that does not exist for the day.
+pub const SINGLE_PART: &str = "(single-part day)";
+
+/// A value that can be returned by a solution part.
+///
+/// Parts return either an [`Option`], or a [`Result`] whose error explains why they failed.
+pub trait PartResult {
+    type Output: Display;
+
+    /// Converts the value into its result, or into the reason of the failure, if any.
+    fn into_result(self) -> Result<Self::Output, Option<String>>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    type Output = T;
+
+    fn into_result(self) -> Result<T, Option<String>> {
+        self.ok_or(None)
+    }
+}
+
+impl<T: Display, E: Display> PartResult for Result<T, E> {
+    type Output = T;
+
+    fn into_result(self) -> Result<T, Option<String>> {
+        self.map_err(|e| Some(e.to_string()))
+    }
+}
+
+pub fn run_part<I: Clone, R: PartResult>(func: impl Fn(I) -> R, input: I, day: Day, part: u8) {
+    let _ = run_and_submit(func, input, day, part);
+}
+
+/// A named implementation of a part, see [`solution!`](crate::solution).
+pub type Variant<'a, R> = (&'static str, &'a dyn Fn(&str) -> R);
+
+/// Runs the variant of a part chosen with `--variant`, or the first one, like [`run_part`].
+///
+/// With `--time` and no `--variant`, the other variants are benched as well and compared to it.
+/// Returns `false` if one of them does not give the same result.
+pub fn run_variants<R: PartResult>(
+    variants: &[Variant<R>],
+    input: &str,
+    day: Day,
+    part: u8,
+) -> bool {
+    let chosen = chosen_variant();
+    let (name, func) = match &chosen {
+        Some(chosen) => variants
+            .iter()
+            .find(|(name, _)| name == chosen)
+            .copied()
+            .unwrap_or_else(|| {
+                if variants.len() > 1 {
+                    eprintln!(
+                        "Part {part} has no variant `{chosen}`, running `{}`.",
+                        variants[0].0
+                    );
+                }
+                variants[0]
+            }),
+        None => variants[0],
+    };
+
+    let (result, duration) = run_and_submit(func, input, day, part);
+
+    let is_timed = env::args().any(|x| x == "--time");
+    if !is_timed || chosen.is_some() || variants.len() < 2 {
+        return true;
+    }
+
+    let width = variants
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    let expected = result.map(|r| r.to_string());
+    print_variant(name, width, &duration, &duration, None);
+
+    let mut agree = true;
+    for (other, func) in variants.iter().filter(|(other, _)| *other != name) {
+        print!("  {other:<width$}");
+        let (result, other_duration, _) = run_timed(
+            |input| func(input).into_result().map(|r| r.to_string()),
+            input,
+            |_| {},
+        );
+        print!("\r");
+
+        let differs = (result != expected).then(|| match result {
+            Ok(result) => result,
+            Err(_) => "✖".to_string(),
+        });
+        agree &= differs.is_none();
+        print_variant(other, width, &duration, &other_duration, differs);
+    }
+    agree
+}
+
+/// Runs the variants of a part like [`run_variants`], fuzzes them with `--fuzz`, or measures
+/// how they scale with `--scale`.
+pub fn run_part_mode<R: PartResult>(
+    variants: &[Variant<R>],
+    input: &str,
+    generate: Option<Generator>,
+    day: Day,
+    part: u8,
+) -> bool {
+    if let Some(iterations) = fuzz_iterations() {
+        return fuzz_variants(variants, generate, day, part, iterations);
+    }
+    if let Some(factors) = scale_factors() {
+        scale_variants(variants, generate, part, &factors);
+        return true;
+    }
+    run_variants(variants, input, day, part)
+}
+
+/// Returns `true` if the part runs on the puzzle input, i.e. it is not fuzzed or scaled.
+#[must_use]
+pub fn uses_puzzle_input() -> bool {
+    fuzz_iterations().is_none() && scale_factors().is_none()
+}
+
+/// Returns the factors given with `--scale 1,2,4,8`, if any.
+#[must_use]
+pub fn scale_factors() -> Option<Vec<usize>> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--scale")?;
+    let factors = args.get(index + 1)?;
+    factors.split(',').map(|n| n.trim().parse().ok()).collect()
+}
+
+/// Benches the variants of a part (or the one chosen with `--variant`) on generated inputs whose
+/// sizes are [`scale::BASE_SIZE`] times each factor, and prints the exponent of their complexity.
+fn scale_variants<R: PartResult>(
+    variants: &[Variant<R>],
+    generate: Option<Generator>,
+    part: u8,
+    factors: &[usize],
+) {
+    let Some(generate) = generate else {
+        println!("Part {part}: ✖ no generator given to `solution!`");
+        return;
+    };
+
+    let chosen = chosen_variant();
+    let variants: Vec<&Variant<R>> = variants
+        .iter()
+        .filter(|(name, _)| chosen.as_ref().is_none_or(|chosen| name == chosen))
+        .collect();
+    let sizes: Vec<usize> = factors.iter().map(|f| f * scale::BASE_SIZE).collect();
+    let width = variants
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+
+    let row_start = |name: &str| format!("  {name:<width$}    ");
+    let label = format!("Part {part}:");
+    let header: String = sizes
+        .iter()
+        .map(|size| format!("{:>10}", format!("n={size}")))
+        .collect();
+    // aligns the sizes with the timings of the rows below.
+    let pad = row_start("").len().saturating_sub(label.len());
+    println!("{label}{:pad$}{header}", "");
+    for (name, func) in variants {
+        print!("{}> {ANSI_ITALIC}benching{ANSI_RESET}", row_start(name));
+        let _ = stdout().flush();
+
+        let times = scale::measure(*func, generate, &sizes);
+        let times_str: String = times
+            .iter()
+            .map(|t| format!("{:>10}", format!("{t:.1?}")))
+            .collect();
+        let exponent = match scale::fit_exponent(&sizes, &times) {
+            Some(k) => format!("~ O(n^{k:.2})"),
+            None => "-".to_string(),
+        };
+        print!("\r{ANSI_CLEAR_LINE}");
+        println!(
+            "{}{times_str}  {ANSI_BOLD}{exponent}{ANSI_RESET}",
+            row_start(name)
+        );
+    }
+}
+
+/// Returns the number of inputs to generate if run with `--fuzz [iterations]`, 1000 by default.
+#[must_use]
+pub fn fuzz_iterations() -> Option<usize> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--fuzz")?;
+    Some(
+        args.get(index + 1)
+            .and_then(|n| n.parse().ok())
+            .unwrap_or(1000),
+    )
+}
+
+/// Checks that the variants of a part agree on the example and on generated inputs, and prints
+/// the shrunk input if they don't. Returns `false` in that case.
+fn fuzz_variants<R: PartResult>(
+    variants: &[Variant<R>],
+    generate: Option<Generator>,
+    day: Day,
+    part: u8,
+    iterations: usize,
+) -> bool {
+    let example: Vec<String> = super::try_read_file("examples", day).into_iter().collect();
+    print!("Part {part}: > {ANSI_ITALIC}fuzzing{ANSI_RESET}");
+    let _ = stdout().flush();
+
+    let found = fuzz::find_counterexample(variants, generate, &example, iterations);
+    print!("\r");
+    match found {
+        Some(counterexample) => {
+            println!("Part {part}: ✖ variants disagree on an {counterexample}");
+            false
+        }
+        None => {
+            let checked = match generate {
+                Some(_) => format!("{} inputs", example.len() + iterations),
+                None => "the example (no generator)".to_string(),
+            };
+            println!(
+                "Part {part}: ✔ {} variant(s) agree on {checked}",
+                variants.len()
+            );
+            true
+        }
+    }
+}
+
+/// Returns the name given with `--variant`, if any.
+fn chosen_variant() -> Option<String> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--variant")?;
+    args.get(index + 1).cloned()
+}
+
+/// Prints how long a variant took compared to the chosen one, and its result if it differs.
+fn print_variant(
+    name: &str,
+    width: usize,
+    base: &Duration,
+    duration: &Duration,
+    differs: Option<String>,
+) {
+    let speedup = base.as_secs_f64() / duration.as_secs_f64().max(f64::MIN_POSITIVE);
+    let mut row = format!(
+        "  {name:<width$}  {:>10}  {speedup:.2}x",
+        format!("{duration:.1?}")
+    );
+    if let Some(result) = differs {
+        row.push_str(&format!("  ✖ different result: {result}"));
+    }
+    // pads the row to overwrite the benching indicator.
+    println!("{row:<pad$}", pad = width + 28);
+}
+
+/// Runs a part, prints its result and submits it if asked to. Returns the result and its timing.
+fn run_and_submit<I: Clone, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    day: Day,
+    part: u8,
+) -> (Result<R::Output, Option<String>>, Duration) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, duration, samples) = run_timed(
+        |input| func(input).into_result(),
+        input,
+        |result| print_result(result, &part_str, ""),
+    );
 
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
-    if let Some(result) = result {
+    if let Ok(result) = &result {
         submit_result(result, day, part);
     }
+    (result, duration)
+}
+
+/// Returns the letters drawn by a multi-line answer, or the answer itself if it draws none.
+fn read_letters(answer: &str) -> String {
+    if !answer.contains('\n') {
+        return answer.to_string();
+    }
+    match ocr::decode_str(answer) {
+        Some(letters) => {
+            println!("Read as: {ANSI_BOLD}{letters}{ANSI_RESET}");
+            letters
+        }
+        None => answer.to_string(),
+    }
+}
+
+/// Reads the puzzle input for a day, or exits with a hint on how to fetch it.
+#[must_use]
+pub fn read_input(day: Day) -> String {
+    match super::try_read_file("inputs", day) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Returns `true` if `part` should run, i.e. no other part was selected with `--part`.
+#[must_use]
+pub fn is_part_selected(part: u8) -> bool {
+    let args: Vec<String> = env::args().collect();
+
+    match args.iter().position(|x| x == "--part") {
+        Some(index) => args.get(index + 1).and_then(|p| p.parse::<u8>().ok()) == Some(part),
+        None => true,
+    }
+}
+
+/// Marks a part as not existing for the day, so that `all` treats the day as complete.
+pub fn print_single_part(part: u8) {
+    println!("Part {part}: - {SINGLE_PART}");
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
@@ -51,13 +365,12 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    // the frames and debug output were shown by the first run, don't repeat them for every sample.
+    viz::set_suspended(true);
+    trace::set_suspended(true);
+
+    let bench_iterations =
+        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -69,6 +382,9 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
+    viz::set_suspended(false);
+    trace::set_suspended(false);
+
     (
         #[allow(clippy::cast_possible_truncation)]
         Duration::from_nanos(average_duration(&timers) as u64),
@@ -92,11 +408,11 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result<T: Display>(result: &Result<T, Option<String>>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        Ok(result) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -116,7 +432,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Err(None) => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -124,6 +440,14 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Err(Some(reason)) => {
+            if is_intermediate_result {
+                print!("{part}: ✖");
+            } else {
+                print!("\r");
+                println!("{part}: ✖ {reason}");
+            }
+        }
     }
 }
 
@@ -162,6 +486,7 @@ fn submit_result<T: Display>(
         process::exit(1);
     }
 
+    let answer = read_letters(&result.to_string());
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(aoc_cli::submit(day, part, &answer))
 }
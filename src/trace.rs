@@ -0,0 +1,115 @@
+//! Debug output of solutions, shown with `cargo solve <day> -v` or `-vv`.
+
+use std::env;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+/// The verbosity of the run, or `UNSET` until it is read from the arguments.
+static VERBOSITY: AtomicU8 = AtomicU8::new(UNSET);
+const UNSET: u8 = u8::MAX;
+
+/// Set while the runner benches a solution, so that its output is not repeated.
+static SUSPENDED: AtomicBool = AtomicBool::new(false);
+
+/// How much debug output is shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// No output, the default.
+    Quiet,
+    /// Output of [`aoc_debug!`](crate::aoc_debug), shown with `-v`.
+    Debug,
+    /// Output of [`aoc_trace!`](crate::aoc_trace) too, shown with `-vv`.
+    Trace,
+}
+
+impl Level {
+    /// Returns the level set by `-v` and `-vv` flags, which add up (`-v -v` is `-vv`).
+    pub fn from_args(args: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        let count: usize = args
+            .into_iter()
+            .map(|arg| match arg.as_ref() {
+                "-v" => 1,
+                "-vv" => 2,
+                _ => 0,
+            })
+            .sum();
+        match count {
+            0 => Self::Quiet,
+            1 => Self::Debug,
+            _ => Self::Trace,
+        }
+    }
+
+    fn from_u8(level: u8) -> Self {
+        match level {
+            0 => Self::Quiet,
+            1 => Self::Debug,
+            _ => Self::Trace,
+        }
+    }
+}
+
+/// Returns the verbosity of the run: [`Level::Quiet`] while the runner benches the solution.
+pub fn verbosity() -> Level {
+    if SUSPENDED.load(Ordering::Relaxed) {
+        return Level::Quiet;
+    }
+    let level = VERBOSITY.load(Ordering::Relaxed);
+    if level != UNSET {
+        return Level::from_u8(level);
+    }
+    let level = Level::from_args(env::args());
+    VERBOSITY.store(level as u8, Ordering::Relaxed);
+    level
+}
+
+/// Returns `true` if the output of `level` is shown.
+pub fn is_enabled(level: Level) -> bool {
+    level != Level::Quiet && verbosity() >= level
+}
+
+/// Silences debug output while the runner benches a solution, see [`verbosity`].
+pub(crate) fn set_suspended(suspended: bool) {
+    SUSPENDED.store(suspended, Ordering::Relaxed);
+}
+
+/// Prints to stderr like `eprintln!` when run with `-v` or `-vv`.
+///
+/// The verbosity is checked at runtime, with an atomic load and a branch, so the call and its
+/// arguments are compiled into every build, benches included. The arguments are only evaluated
+/// when the output is shown.
+#[macro_export]
+macro_rules! aoc_debug {
+    ($($arg:tt)*) => {
+        if $crate::trace::is_enabled($crate::trace::Level::Debug) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Prints to stderr like `eprintln!` when run with `-vv`.
+///
+/// Like [`aoc_debug!`](crate::aoc_debug), it is compiled into every build and checked at runtime.
+#[macro_export]
+macro_rules! aoc_trace {
+    ($($arg:tt)*) => {
+        if $crate::trace::is_enabled($crate::trace::Level::Trace) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Level;
+
+    #[test]
+    fn adds_up_verbose_flags() {
+        assert_eq!(Level::from_args(["05", "--time"]), Level::Quiet);
+        assert_eq!(Level::from_args(["05", "-v"]), Level::Debug);
+        assert_eq!(Level::from_args(["-vv", "--part", "1"]), Level::Trace);
+        assert_eq!(Level::from_args(["-v", "-v"]), Level::Trace);
+        assert_eq!(Level::from_args(["-v", "-vv"]), Level::Trace);
+    }
+}
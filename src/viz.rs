@@ -0,0 +1,325 @@
+//! Visualisation of grids and simulations, enabled with `cargo solve <day> --viz`.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::{env, thread};
+
+use crate::grid::{Grid, Pos};
+use crate::Day;
+
+/// Whether the solution was run with `--viz`, read from the arguments on first use.
+static REQUESTED: OnceLock<bool> = OnceLock::new();
+
+/// Set while the runner benches a solution, so that its frames are not shown again.
+static SUSPENDED: AtomicBool = AtomicBool::new(false);
+
+/// A colour, as its red, green and blue components.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Self = Self::rgb(0, 0, 0);
+    pub const WHITE: Self = Self::rgb(255, 255, 255);
+    pub const GREY: Self = Self::rgb(96, 96, 96);
+    pub const RED: Self = Self::rgb(220, 50, 47);
+    pub const GREEN: Self = Self::rgb(80, 200, 80);
+    pub const BLUE: Self = Self::rgb(38, 139, 210);
+    pub const YELLOW: Self = Self::rgb(250, 210, 50);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+}
+
+/// How a cell is drawn: the character shown in the terminal, and its colour there and in images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cell {
+    pub c: char,
+    pub color: Color,
+}
+
+impl Cell {
+    pub const fn new(c: char, color: Color) -> Self {
+        Self { c, color }
+    }
+}
+
+/// Returns `true` if the solution was run with `--viz`, and is not being benched.
+#[must_use]
+pub fn is_enabled() -> bool {
+    let requested = *REQUESTED.get_or_init(|| env::args().any(|x| x == "--viz"));
+    requested && !SUSPENDED.load(Ordering::Relaxed)
+}
+
+/// Turns visualisations off while the runner benches a solution, see [`is_enabled`].
+pub(crate) fn set_suspended(suspended: bool) {
+    SUSPENDED.store(suspended, Ordering::Relaxed);
+}
+
+/// Renders `grid` for the terminal, drawing each cell with `draw` in its 24-bit ANSI colour.
+pub fn render<T>(grid: &Grid<T>, mut draw: impl FnMut(Pos, &T) -> Cell) -> String {
+    let mut out = String::new();
+    for (y, row) in grid.rows().enumerate() {
+        let mut current = None;
+        for (x, value) in row.iter().enumerate() {
+            let cell = draw((x, y), value);
+            if current != Some(cell.color) {
+                let Color { r, g, b } = cell.color;
+                out.push_str(&format!("\x1b[38;2;{r};{g};{b}m"));
+                current = Some(cell.color);
+            }
+            out.push(cell.c);
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+/// The frames of a simulation, shown in the terminal as they are added and saved as images.
+///
+/// Everything is a no-op unless visualisations are enabled, so solutions can keep their frames.
+/// Building a frame can still be costly: check [`Animation::is_enabled`] first in hot loops.
+pub struct Animation {
+    day: Day,
+    delay: Duration,
+    scale: usize,
+    frames: Vec<Grid<Color>>,
+    enabled: bool,
+}
+
+impl Animation {
+    /// Creates an empty animation, shown with a 50ms delay between frames and saved at 4 pixels
+    /// per cell.
+    #[must_use]
+    pub fn new(day: Day) -> Self {
+        Self {
+            day,
+            delay: Duration::from_millis(50),
+            scale: 4,
+            frames: vec![],
+            enabled: is_enabled(),
+        }
+    }
+
+    /// Sets the delay between two frames, in the terminal and in GIFs.
+    #[must_use]
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Sets the size in pixels of the square drawn for each cell in images.
+    #[must_use]
+    pub fn scale(mut self, scale: usize) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Adds a frame: clears the terminal, shows the grid on stderr and waits for the delay.
+    pub fn frame<T>(&mut self, grid: &Grid<T>, mut draw: impl FnMut(Pos, &T) -> Cell) {
+        if !self.enabled {
+            return;
+        }
+        let mut colors = Vec::with_capacity(grid.width() * grid.height());
+        let text = render(grid, |pos, value| {
+            let cell = draw(pos, value);
+            colors.push(cell.color);
+            cell
+        });
+
+        let mut stderr = io::stderr().lock();
+        let _ = write!(stderr, "\x1b[H\x1b[2J{text}");
+        let _ = stderr.flush();
+        thread::sleep(self.delay);
+
+        self.frames
+            .push(Grid::new(grid.width(), grid.height(), colors).unwrap());
+    }
+
+    /// Saves the frames to `data/viz/<day>/`, as `<name>.gif`, or as `<name>.png` if there is a
+    /// single frame. Returns the path written, or [`None`] if there was nothing to save.
+    pub fn save(&self, name: &str) -> io::Result<Option<PathBuf>> {
+        let path = match self.frames.as_slice() {
+            [] => return Ok(None),
+            [image] => {
+                let path = output_path(self.day, &format!("{name}.png"))?;
+                write_png(&path, image, self.scale)?;
+                path
+            }
+            frames => {
+                let path = output_path(self.day, &format!("{name}.gif"))?;
+                write_gif(&path, frames, self.delay, self.scale)?;
+                path
+            }
+        };
+        eprintln!("Saved {} frame(s) to {path:?}", self.frames.len());
+        Ok(Some(path))
+    }
+}
+
+/// Returns the path of `file` in `data/viz/<day>/`, creating the folder if needed.
+pub fn output_path(day: Day, file: &str) -> io::Result<PathBuf> {
+    let dir = Path::new("data").join("viz").join(day.to_string());
+    fs::create_dir_all(&dir)?;
+    Ok(dir.join(file))
+}
+
+/// Writes `image` as a binary PPM, with `scale` x `scale` pixels per cell.
+pub fn write_ppm(path: &Path, image: &Grid<Color>, scale: usize) -> io::Result<()> {
+    let (width, height, pixels) = pixels(image, scale);
+    let mut file = BufWriter::new(File::create(path)?);
+    write!(file, "P6\n{width} {height}\n255\n")?;
+    file.write_all(&pixels)?;
+    file.flush()
+}
+
+/// Writes `image` as a PNG, with `scale` x `scale` pixels per cell.
+pub fn write_png(path: &Path, image: &Grid<Color>, scale: usize) -> io::Result<()> {
+    let (width, height, pixels) = pixels(image, scale);
+    let (width, height) = (dimension::<u32>(width)?, dimension::<u32>(height)?);
+
+    let mut encoder = png::Encoder::new(BufWriter::new(File::create(path)?), width, height);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer.write_image_data(&pixels).map_err(io::Error::other)
+}
+
+/// Writes `frames` as a looping animated GIF, with `scale` x `scale` pixels per cell.
+/// The frames must all have the size of the first one.
+pub fn write_gif(
+    path: &Path,
+    frames: &[Grid<Color>],
+    delay: Duration,
+    scale: usize,
+) -> io::Result<()> {
+    let Some(first) = frames.first() else {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "no frames"));
+    };
+    let (width, height) = (first.width() * scale, first.height() * scale);
+    let (width, height) = (dimension::<u16>(width)?, dimension::<u16>(height)?);
+    // GIF delays are in hundredths of a second.
+    let delay = u16::try_from(delay.as_millis() / 10).unwrap_or(u16::MAX);
+
+    let file = BufWriter::new(File::create(path)?);
+    let mut encoder = gif::Encoder::new(file, width, height, &[]).map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+    for image in frames {
+        if (image.width(), image.height()) != (first.width(), first.height()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "frames have different sizes",
+            ));
+        }
+        let (_, _, pixels) = pixels(image, scale);
+        let mut frame = gif::Frame::from_rgb_speed(width, height, &pixels, 10);
+        frame.delay = delay;
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+    Ok(())
+}
+
+/// Returns the width, height and RGB bytes of `image` drawn with `scale` x `scale` pixels per cell.
+fn pixels(image: &Grid<Color>, scale: usize) -> (usize, usize, Vec<u8>) {
+    let (width, height) = (image.width() * scale, image.height() * scale);
+    let mut pixels = Vec::with_capacity(width * height * 3);
+    for row in image.rows() {
+        let line: Vec<u8> = row
+            .iter()
+            .flat_map(|c| [c.r, c.g, c.b].repeat(scale))
+            .collect();
+        for _ in 0..scale {
+            pixels.extend_from_slice(&line);
+        }
+    }
+    (width, height, pixels)
+}
+
+fn dimension<T: TryFrom<usize>>(size: usize) -> io::Result<T> {
+    T::try_from(size).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "image is too large"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, write_gif, write_ppm, Cell, Color};
+    use crate::grid::Grid;
+    use std::time::Duration;
+    use std::{env, fs};
+
+    fn checkerboard() -> Grid<Color> {
+        Grid::parse(
+            "#.\n.#",
+            |c| if c == '#' { Color::RED } else { Color::BLACK },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn renders_with_colour_changes_only() {
+        let grid = Grid::parse("##.", |c| c == '#').unwrap();
+        let text = render(&grid, |_, lit| {
+            if *lit {
+                Cell::new('#', Color::WHITE)
+            } else {
+                Cell::new('.', Color::GREY)
+            }
+        });
+        assert_eq!(
+            text,
+            "\x1b[38;2;255;255;255m##\x1b[38;2;96;96;96m.\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn writes_scaled_ppm() {
+        let path = env::temp_dir().join("advent_of_code_viz_test.ppm");
+        write_ppm(&path, &checkerboard(), 2).unwrap();
+        let bytes = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let (header, pixels) = bytes.split_at(b"P6\n4 4\n255\n".len());
+        assert_eq!(header, b"P6\n4 4\n255\n");
+        assert_eq!(pixels.len(), 4 * 4 * 3);
+        assert_eq!(&pixels[..6], &[220, 50, 47, 220, 50, 47]);
+        assert_eq!(&pixels[6 * 3..7 * 3], &[0, 0, 0]);
+        assert_eq!(&pixels[10 * 3..11 * 3], &[220, 50, 47]);
+        assert_eq!(&pixels[12 * 3..13 * 3], &[0, 0, 0]);
+    }
+
+    #[test]
+    fn writes_gif_frames_of_one_size() {
+        let path = env::temp_dir().join("advent_of_code_viz_test.gif");
+        let frames = [checkerboard(), checkerboard().transpose()];
+        write_gif(&path, &frames, Duration::from_millis(100), 3).unwrap();
+        let bytes = fs::read(&path).unwrap();
+        assert!(bytes.starts_with(b"GIF89a"));
+
+        let frames = [checkerboard(), Grid::filled(3, 1, Color::BLUE)];
+        assert!(write_gif(&path, &frames, Duration::ZERO, 1).is_err());
+        fs::remove_file(&path).unwrap();
+    }
+}